@@ -0,0 +1,102 @@
+use aoc_common::random::Rng;
+use aoc_common::{trace, Answer, ParseError};
+
+fn str_to_numeric(s: &str) -> u32 {
+    match s {
+        "1" | "one"   => 1,
+        "2" | "two"   => 2,
+        "3" | "three" => 3,
+        "4" | "four"  => 4,
+        "5" | "five"  => 5,
+        "6" | "six"   => 6,
+        "7" | "seven" => 7,
+        "8" | "eight" => 8,
+        "9" | "nine"  => 9,
+        _ => 0
+    }
+}
+
+/// Sum the calibration values of every line, where the first and last
+/// occurrences of any of `valid_matches` form a two-digit number.
+fn calibration_sum(input: &str, valid_matches: &[&str]) -> Result<u64, ParseError> {
+    let mut sum = 0;
+
+    for line in input.lines() {
+        // Find first digit
+        let mut matches = vec![];
+        for m in valid_matches {
+            line.match_indices(m).for_each(|element| { matches.push(element); });
+        }
+        matches.sort_by_key(|a| a.0);
+        let (Some(first), Some(last)) = (matches.first(), matches.last()) else {
+            return Err(ParseError::at(input, line, "no digit found on this line"));
+        };
+        let result = str_to_numeric(first.1) * 10 + str_to_numeric(last.1);
+        trace!("{} {} {} -> {}", first.1, line, last.1, result);
+        sum += u64::from(result);
+    }
+    Ok(sum)
+}
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    let valid_matches = ["1", "2", "3", "4", "5", "6", "7", "8", "9"];
+    Ok(calibration_sum(input, &valid_matches)?.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    let valid_matches = [
+        "1", "2", "3", "4", "5", "6", "7", "8", "9",
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    Ok(calibration_sum(input, &valid_matches)?.into())
+}
+
+/// Random calibration document of `size` lines. Each line has at least one digit, for
+/// part 1, among random letters and spelled out digits, some of which overlap like in
+/// "twone".
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const WORDS: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    let mut input = String::new();
+    for _ in 0..size {
+        let pieces = rng.range(1..=8);
+        let digit_piece = rng.index(pieces);
+        let mut line = String::new();
+        for piece in 0..pieces {
+            if piece == digit_piece || rng.chance(0.2) {
+                line.push(char::from(rng.range(b'1'..=b'9')));
+            } else if rng.chance(0.4) {
+                let word = rng.choose(&WORDS);
+                // Share a letter with the previous word now and then
+                if line.ends_with(&word[..1]) && rng.chance(0.5) {
+                    line.push_str(&word[1..]);
+                } else {
+                    line.push_str(word);
+                }
+            } else {
+                for _ in 0..rng.range(1..=4) {
+                    line.push(char::from(rng.range(b'a'..=b'z')));
+                }
+            }
+        }
+        input += &line;
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(142.into()));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(part2(include_str!("../example2.txt")), Ok(281.into()));
+    }
+}
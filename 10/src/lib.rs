@@ -0,0 +1,403 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::export::{Picture, Rgb};
+use aoc_common::geom::{Direction, Point};
+use aoc_common::grid::Position;
+use aoc_common::log::{self, Level};
+use aoc_common::random::{self, Rng};
+use aoc_common::{debug, trace, Answer, Grid, ParseError};
+
+type Tiles = Grid<char>;
+
+/// Returns the position after the step, and the direction of the next step
+fn get_next_step(
+    position: Position,
+    direction: Direction,
+    map: &Tiles,
+) -> Option<(Position, Direction)> {
+    use Direction::*;
+
+    // Stepping off the map is not a valid step
+    let next = map.step(position, direction)?;
+    let next_direction = match (map[next], direction) {
+        ('|', Up) => Up,
+        ('|', Down) => Down,
+        ('J', Down) => Left,
+        ('J', Right) => Up,
+        ('L', Left) => Up,
+        ('L', Down) => Right,
+        ('F', Up) => Right,
+        ('F', Left) => Down,
+        ('-', Left) => Left,
+        ('-', Right) => Right,
+        ('7', Right) => Down,
+        ('7', Up) => Left,
+        _ => return None,
+    };
+
+    Some((next, next_direction))
+}
+
+/// Error for a starting tile which does not connect to exactly two pipes, which makes
+/// it impossible to tell what pipe it is.
+fn start_error((x, y): Position) -> ParseError {
+    ParseError::new(
+        y + 1,
+        x + 1,
+        "the starting tile 'S' must connect to exactly two pipes",
+    )
+}
+
+// Try all directions and pick the first valid one
+fn get_first_step(
+    position: Position,
+    map: &Tiles,
+) -> Result<(Direction, (Position, Direction)), ParseError> {
+    for first_direction in Direction::ALL {
+        if let Some(v) = get_next_step(position, first_direction, map) {
+            return Ok((first_direction, v));
+        }
+    }
+    Err(start_error(position))
+}
+
+fn get_starting_pos_type(position: Position, map: &Tiles) -> Result<char, ParseError> {
+    use Direction::*;
+    let mut possible_directions = vec![];
+    for first_direction in Direction::ALL {
+        if get_next_step(position, first_direction, map).is_some() {
+            possible_directions.push(first_direction);
+        }
+    }
+    debug!(
+        "possible directions from the start: {:?}",
+        possible_directions
+    );
+    match possible_directions[..] {
+        [Up, Down] => Ok('|'),
+        [Up, Right] => Ok('L'),
+        [Up, Left] => Ok('J'),
+        [Right, Left] => Ok('-'),
+        [Right, Down] => Ok('F'),
+        [Down, Left] => Ok('7'),
+        _ => Err(start_error(position)),
+    }
+}
+
+fn parse_tiles(input: &str) -> Result<Tiles, ParseError> {
+    let tiles = Grid::parse(input, |c| "|-LJ7F.S".contains(c).then_some(c))?;
+    let start = match tiles.position(|c| *c == 'S') {
+        Some(start) if input.matches('S').count() == 1 => start,
+        _ => {
+            return Err(ParseError::new(
+                1,
+                1,
+                "expected exactly one starting tile 'S'",
+            ))
+        }
+    };
+    // The loop goes through the start, so it must connect to two of its neighbours
+    get_starting_pos_type(start, &tiles)?;
+
+    Ok(tiles)
+}
+
+/// Tiles on the main loop, with the tile type and direction of travel
+type PathTiles = HashMap<Position, (char, Direction)>;
+
+/// Follow the loop from the starting tile until we get back to it.
+/// Returns the list of steps and the tiles along the path, with the 'S'
+/// replaced by its actual value to simplify the enclosed area calculation logic.
+fn trace_loop(map: &Tiles) -> Result<(Vec<Direction>, PathTiles), ParseError> {
+    // Start from s
+    let start_pos = map.position(|c| *c == 'S').unwrap();
+    debug!("start at {:?}", start_pos);
+
+    let (first_direction, (mut position, mut direction)) = get_first_step(start_pos, map)?;
+
+    // Store the steps in a vector for part 2
+    let mut steps: Vec<Direction> = vec![first_direction];
+    let mut path_tiles: PathTiles = HashMap::new();
+    path_tiles.insert(start_pos, (map[start_pos], first_direction));
+    loop {
+        trace!("{:?} going {:?}", position, direction);
+        steps.push(direction);
+        path_tiles.insert(position, (map[position], direction));
+        match get_next_step(position, direction, map) {
+            Some(next) => {
+                (position, direction) = next;
+            }
+            None => break,
+        }
+    }
+    let start_tile = get_starting_pos_type(start_pos, map)?;
+    debug!("start tile is '{}'", start_tile);
+
+    // Replace the S by its actual value to simplify the enclosed area calculation logic
+    path_tiles.insert(start_pos, (start_tile, first_direction));
+
+    Ok((steps, path_tiles))
+}
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    let map = parse_tiles(input)?;
+    let (steps, _) = trace_loop(&map)?;
+
+    debug!("path length {}", steps.len());
+
+    // Maximum distance from the start
+    Ok(steps.len().div_ceil(2).into())
+}
+
+/// Tiles enclosed by the loop
+fn enclosed_tiles(map: &Tiles, path_tiles: &PathTiles) -> Vec<Position> {
+    let mut tiles_in: Vec<Position> = vec![];
+
+    for j in 0..map.height() {
+        for i in 0..map.width() {
+            // Ignore if part of the path
+            if path_tiles.contains_key(&(i, j)) {
+                continue;
+            }
+
+            // The logic is the following:
+            // If we have an odd number of path crossings in each cardinal direction,
+            // then this tile is enclosed in the loop.
+            // To quantify crossings, consider casting a ray from the tile to the right edge
+            // of the map.
+            // + If we encounter a '|', we count this as 1.
+            // + If we encounter a 'F' or 'J', we count the tile as 0.5 (a F followed by a J counts as a |).
+            // + If we encounter a '7' or 'L', we count the tile as -0.5 (a 7 followed by a L counts as a |,
+            //   but a L followed by a J counts as nothing: the path does not cross but turns around).
+            // + If we encounter a '-', we count the tile as 0 (the path did not cross or turn around).
+            // And similarly for all 4 directions.
+            // (In the code we multiply these values by 2 and then divide the sum by 2 at the end to avoid floats)
+
+            // Cast horizontal ray from the left
+            // Count straight wall sections of the path
+            let mut num_walls_left = 0;
+            for x in 0..i {
+                if let Some(tile) = path_tiles.get(&(x, j)) {
+                    match tile.0 {
+                        '|' => num_walls_left += 2,
+                        'J' | 'F' => num_walls_left += 1,
+                        '7' | 'L' => num_walls_left -= 1,
+                        '-' => (),
+                        _ => panic!(),
+                    }
+                }
+            }
+            num_walls_left /= 2;
+
+            // Repeat on right side
+            let mut num_walls_right = 0;
+            for x in i + 1..map.width() {
+                if let Some(tile) = path_tiles.get(&(x, j)) {
+                    match tile.0 {
+                        '|' => num_walls_right += 2,
+                        'J' | 'F' => num_walls_right += 1,
+                        '7' | 'L' => num_walls_right -= 1,
+                        '-' => (),
+                        _ => panic!(),
+                    }
+                }
+            }
+            num_walls_right /= 2;
+
+            // Cast vertical ray from the top
+            // Count horizontal/corner sections of the path
+
+            let mut num_walls_top = 0;
+            for y in 0..j {
+                if let Some(tile) = path_tiles.get(&(i, y)) {
+                    match tile.0 {
+                        '-' => num_walls_top += 2,
+                        'J' | 'F' => num_walls_top += 1,
+                        '7' | 'L' => num_walls_top -= 1,
+                        '|' => (),
+                        _ => panic!(),
+                    }
+                }
+            }
+            num_walls_top /= 2;
+
+            // Repeat on bottom
+            let mut num_walls_bottom = 0;
+            for y in j + 1..map.height() {
+                if let Some(tile) = path_tiles.get(&(i, y)) {
+                    match tile.0 {
+                        '-' => num_walls_bottom += 2,
+                        'J' | 'F' => num_walls_bottom -= 1,
+                        '7' | 'L' => num_walls_bottom += 1,
+                        '|' => (),
+                        _ => panic!(),
+                    }
+                }
+            }
+            num_walls_bottom /= 2;
+            trace!(
+                "({}, {}): walls left {}, right {}, top {}, bottom {}",
+                i,
+                j,
+                num_walls_left,
+                num_walls_right,
+                num_walls_top,
+                num_walls_bottom
+            );
+
+            if num_walls_bottom % 2 != 0
+                && num_walls_right % 2 != 0
+                && num_walls_left % 2 != 0
+                && num_walls_top % 2 != 0
+            {
+                tiles_in.push((i, j));
+            }
+        }
+    }
+    tiles_in
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    let map = parse_tiles(input)?;
+    let (_, path_tiles) = trace_loop(&map)?;
+    let tiles_in = enclosed_tiles(&map, &path_tiles);
+
+    // Draw a diagram like in the examples
+    if log::enabled(Level::Debug) {
+        let mut diagram = String::new();
+        for j in 0..map.height() {
+            diagram.push('\n');
+            for i in 0..map.width() {
+                if path_tiles.contains_key(&(i, j)) {
+                    diagram.push(path_tiles[&(i, j)].0);
+                } else if tiles_in.contains(&(i, j)) {
+                    diagram.push('I');
+                } else {
+                    diagram.push('O');
+                }
+            }
+        }
+        debug!("enclosed tiles:{}", diagram);
+    }
+
+    Ok(tiles_in.len().into())
+}
+
+/// Directions in which a pipe connects to its neighbours
+fn connections(tile: char) -> &'static [Direction] {
+    use Direction::*;
+    match tile {
+        '|' => &[Up, Down],
+        '-' => &[Right, Left],
+        'L' => &[Up, Right],
+        'J' => &[Up, Left],
+        '7' => &[Down, Left],
+        'F' => &[Right, Down],
+        _ => &[],
+    }
+}
+
+/// Draw the map of part 2 with 3x3 pixels per tile, showing the shape of the pipes:
+/// the loop stands out from the other pipes, and the tiles it encloses are
+/// highlighted.
+pub fn render(input: &str) -> Result<Picture, ParseError> {
+    const OUTSIDE: Rgb = Rgb::new(24, 24, 40);
+    const INSIDE: Rgb = Rgb::new(40, 160, 80);
+    const LOOP: Rgb = Rgb::new(255, 200, 40);
+    const JUNK: Rgb = Rgb::new(80, 80, 110);
+
+    let map = parse_tiles(input)?;
+    let (_, path_tiles) = trace_loop(&map)?;
+    let tiles_in: HashSet<_> = enclosed_tiles(&map, &path_tiles).into_iter().collect();
+
+    let mut image = Grid::new(map.width() * 3, map.height() * 3, OUTSIDE);
+    for ((i, j), &tile) in map.iter() {
+        let (tile, color) = match path_tiles.get(&(i, j)) {
+            Some(&(tile, _)) => (tile, LOOP),
+            None => (tile, JUNK),
+        };
+        let center = (3 * i + 1, 3 * j + 1);
+        if tiles_in.contains(&(i, j)) {
+            for y in 3 * j..3 * j + 3 {
+                for x in 3 * i..3 * i + 3 {
+                    image[(x, y)] = INSIDE;
+                }
+            }
+        } else if !connections(tile).is_empty() {
+            image[center] = color;
+            for &direction in connections(tile) {
+                let arm = image.step(center, direction).unwrap();
+                image[arm] = color;
+            }
+        }
+    }
+    Ok(Picture::Raster { image, scale: 2 })
+}
+
+/// Random map of `size` by `size` tiles, with a loop that can run along the sides of
+/// the map, and pipes of all sorts around it, and inside it.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut map = Grid::from_fn(size, size, |_| {
+        *rng.choose(&['|', '-', 'L', 'J', '7', 'F', '.'])
+    });
+    let path = random::random_loop(rng, size, size);
+    let direction = |from: Position, to: Position| {
+        Direction::ALL
+            .into_iter()
+            .find(|&direction| Point::from(from).step(direction) == Some(Point::from(to)))
+            .unwrap()
+    };
+    for (i, &position) in path.iter().enumerate() {
+        let before = path[(i + path.len() - 1) % path.len()];
+        let after = path[(i + 1) % path.len()];
+        let arms = [direction(position, before), direction(position, after)];
+        let tile = "|-LJ7F".chars().find(|&tile| {
+            let connections = connections(tile);
+            arms.iter().all(|arm| connections.contains(arm))
+        });
+        map[position] = tile.unwrap();
+    }
+
+    // Only the two ends of the loop may connect to the start
+    let start = path[rng.index(path.len())];
+    for direction in Direction::ALL {
+        if let Some(next) = map.step(start, direction) {
+            if !path.contains(&next) {
+                map[next] = '.';
+            }
+        }
+    }
+    map[start] = 'S';
+    map.to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::ParseError;
+
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(4.into()));
+        assert_eq!(part1(include_str!("../example2.txt")), Ok(8.into()));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(part2(include_str!("../example3.txt")), Ok(4.into()));
+        assert_eq!(part2(include_str!("../example4.txt")), Ok(8.into()));
+        assert_eq!(part2(include_str!("../example5.txt")), Ok(10.into()));
+    }
+
+    #[test]
+    fn test_dead_end_start() {
+        let error = ParseError::new(
+            1,
+            1,
+            "the starting tile 'S' must connect to exactly two pipes",
+        );
+        assert_eq!(part1("S.\n.."), Err(error.clone()));
+        assert_eq!(part2("S-7\n..|\n..."), Err(error));
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use aoc_common::geom::Point;
+use aoc_common::random::Rng;
+use aoc_common::{check_grid, debug, trace, Answer, Grid, ParseError};
+
+// Double every row where there are no galaxies
+// Double every column where there are no galaxies
+fn expand(input: &str) -> String {
+    let mut expanded_rows = String::new();
+    // Rows:
+    for row in input.split_inclusive('\n').rev() {
+        expanded_rows.push_str(row);
+        if !row.contains('#') {
+            expanded_rows.push_str(row);
+        }
+    }
+
+    // Columns:
+    // Rotate the map and repeat
+    let mut expanded_cols = String::new();
+    let width = input.lines().next().unwrap().len();
+    // let mut rotated = vec![];
+    for i in 0..width {
+        let col: String = expanded_rows.chars().skip(i).step_by(width + 1).collect();
+        expanded_cols.push_str((col.clone() + "\n").as_str());
+
+        if !col.contains('#') {
+            expanded_cols.push_str((col + "\n").as_str());
+        }
+    }
+    // Rotate back
+    let mut expanded = String::new();
+    let width = expanded_cols.lines().next().unwrap().len();
+    for i in 0..width {
+        let row: String = expanded_cols
+            .chars()
+            .rev()
+            .skip(1 + i)
+            .step_by(width + 1)
+            .collect();
+        // Mirror
+        let row: String = row.chars().rev().collect();
+        expanded.push_str((row + "\n").as_str());
+    }
+
+    expanded
+}
+
+type GalaxyId = usize;
+type Position = Point<usize>;
+// Find the galaxies in universe and store them in a hashmap of <Identifier, Position>
+fn assign_numbers(universe: &str) -> HashMap<GalaxyId, Position> {
+    let mut map = HashMap::new();
+    universe.lines().enumerate().for_each(|(y, line)| {
+        line.chars().enumerate().for_each(|(x, c)| {
+            if c == '#' {
+                map.insert(map.len() + 1, Point::new(x, y));
+            }
+        })
+    });
+    map
+}
+
+fn get_pairs(galaxies: &HashMap<GalaxyId, Position>) -> Vec<(GalaxyId, GalaxyId)> {
+    let mut pairs = vec![];
+    for i in galaxies.keys() {
+        for j in galaxies.keys().filter(|x| *x > i) {
+            pairs.push((*i, *j));
+        }
+    }
+    pairs
+}
+
+fn expanded_manhattan_distance(
+    a: &Position,
+    b: &Position,
+    empty_rows: &[usize],
+    empty_columns: &[usize],
+    expansion_factor: usize,
+) -> usize {
+    let x_low = a.x.min(b.x);
+    let x_high = a.x.max(b.x);
+    let y_low = a.y.min(b.y);
+    let y_high = a.y.max(b.y);
+    let mut dist = 0;
+    for j in y_low..=y_high {
+        if empty_rows.contains(&j) {
+            dist += expansion_factor;
+        } else {
+            dist += 1;
+        }
+    }
+
+    for i in x_low..=x_high {
+        if empty_columns.contains(&i) {
+            dist += expansion_factor;
+        } else {
+            dist += 1;
+        }
+    }
+    dist - 2 // Subtract the first step (vertical and horizontal)
+}
+
+fn find_empty_rows(input: &str) -> Vec<usize> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, row)| !row.contains('#'))
+        .map(|(idx, _)| idx)
+        .collect::<Vec<usize>>()
+}
+
+fn find_empty_columns(input: &str) -> Vec<usize> {
+    // Rotate the input so we can handle columns as lines
+    let mut rotated = String::new();
+    let width = input.lines().next().unwrap().len();
+    for i in 0..width {
+        let col: String = input.chars().skip(i).step_by(width + 1).collect();
+        rotated.push_str((col + "\n").as_str());
+    }
+    find_empty_rows(&rotated)
+}
+
+fn is_tile(c: char) -> bool {
+    c == '.' || c == '#'
+}
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    check_grid(input, is_tile)?;
+    let expanded = expand(input);
+    let galaxies = assign_numbers(&expanded);
+    let pairs = get_pairs(&galaxies);
+    debug!("{} galaxies, {} pairs", galaxies.len(), pairs.len());
+
+    let distances = pairs.iter().fold(HashMap::new(), |mut map, pair| {
+        map.insert(
+            *pair,
+            galaxies[&pair.0].manhattan_distance(galaxies[&pair.1]),
+        );
+        map
+    });
+
+    Ok(distances.values().sum::<usize>().into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    check_grid(input, is_tile)?;
+    let galaxies = assign_numbers(input);
+    let empty_rows = find_empty_rows(input);
+    let empty_columns = find_empty_columns(input);
+    debug!("empty rows: {:?}", empty_rows);
+    debug!("empty columns: {:?}", empty_columns);
+
+    let pairs = get_pairs(&galaxies);
+
+    let mut total_dist = 0;
+    for (a, b) in pairs {
+        let dist = expanded_manhattan_distance(
+            &galaxies[&a],
+            &galaxies[&b],
+            &empty_rows,
+            &empty_columns,
+            1000000,
+        );
+        trace!("({} {}) -> {}", a, b, dist);
+        total_dist += dist;
+    }
+    Ok(total_dist.into())
+}
+
+/// Random image of `size` by `size` tiles, with about one galaxy in 50 tiles, and some
+/// rows and columns without any.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let empty_rows: Vec<bool> = (0..size).map(|_| rng.chance(0.05)).collect();
+    let empty_columns: Vec<bool> = (0..size).map(|_| rng.chance(0.05)).collect();
+    let image = Grid::from_fn(size, size, |(x, y)| {
+        if !empty_rows[y] && !empty_columns[x] && rng.chance(0.02) {
+            '#'
+        } else {
+            '.'
+        }
+    });
+    image.to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(374.into()));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(part2(include_str!("../example.txt")), Ok(82000210.into()));
+    }
+}
@@ -0,0 +1,303 @@
+use aoc_common::memo::{Memo, Stats};
+use aoc_common::random::Rng;
+use aoc_common::{debug, parse_number, trace, Answer, ParseError};
+
+fn find_good_combinations(counts: &[usize], states: &str) -> Option<Vec<String>> {
+    // Determine if `states` could be a fit for `counts`.
+    let mut cur_counts = vec![];
+    let mut contiguous = 0;
+    let mut cur_group = 0;
+
+    // First, determine whether these `states` are consistent with counts, starting
+    // from the left of the row. In any subgroup, if the number of contiguous broken
+    // machines is different from the corresponding group in the input (`counts`), then
+    // this configuration is impossible and we can return now.
+    for c in states.chars() {
+        if c == '?' {
+            // If we encounter an unknown state, we cannot tell whether this is a good combination
+            // -> Continue to algorithm
+            break;
+        }
+        if c == '#' {
+            contiguous += 1;
+            if cur_group < counts.len() && contiguous > counts[cur_group] {
+                // We are in the case where the current group of broken
+                // machines is larger than the corresponding count in `counts`.
+                return None;
+            }
+        } else if c == '.' && contiguous > 0 {
+            if cur_group < counts.len() && contiguous != counts[cur_group] {
+                // This subgroup is broken by a '.' but doesn't match the input `counts`
+                // -> Abort early
+                return None;
+            }
+
+            cur_counts.push(contiguous);
+            contiguous = 0;
+            cur_group += 1;
+        }
+    }
+
+    // If we have no unknowns, we can go ahead and calculate the counts, and see
+    // if they match the input.
+    if !states.contains('?') {
+        let mut cur_counts = vec![];
+        let mut contiguous: usize = 0;
+        for c in states.chars() {
+            if c == '#' {
+                contiguous += 1;
+            } else if c == '.' && contiguous > 0 {
+                cur_counts.push(contiguous);
+                contiguous = 0;
+            }
+        }
+        if contiguous > 0 {
+            cur_counts.push(contiguous);
+        }
+        if cur_counts == *counts {
+            trace!("arrangement {}", states);
+            return Some(vec![states.to_string()]);
+        } else {
+            return None;
+        }
+    }
+
+    // The actual algorithm:
+    // Find the first '?' in the states, and compute the resulting configuration
+    // assuming this machine is broken, or working.
+    let mut ret = vec![];
+
+    // Take the first '?' in the states and find combinations in either case ('.' or '#')
+    let idx = states.chars().position(|c| c == '?').unwrap(); // safe to unwrap since we would've returned earlier if there was no '?'
+
+    // Try with this char swapped with a #
+    let mut states_broken = states[..idx].to_string();
+    states_broken.push('#');
+    states_broken.push_str(&states[idx + 1..]);
+    let good_broken = find_good_combinations(counts, &states_broken);
+
+    // Try with this char swapped with a .
+    let mut states_working = states[..idx].to_string();
+    states_working.push('.');
+    states_working.push_str(&states[idx + 1..]);
+    let good_working = find_good_combinations(counts, &states_working);
+
+    if good_broken.is_none() && good_working.is_none() {
+        None
+    } else {
+        if let Some(v) = good_broken {
+            ret.extend(v);
+        }
+        if let Some(v) = good_working {
+            ret.extend(v);
+        }
+        Some(ret)
+    }
+}
+
+/// Arrangements already counted, for the counts and springs left of a row, and the
+/// length of the group of damaged springs being read. The keys borrow from the row.
+type CountMemo<'a> = Memo<(&'a [usize], &'a str, usize), usize>;
+
+fn find_good_combinations_count<'a>(
+    counts: &'a [usize],
+    states: &'a str,
+    group_len: usize,
+    memo: &mut CountMemo<'a>,
+) -> usize {
+    // Default cases:
+    // Counts is empty
+    if counts.is_empty() {
+        // This means all groups so far have matched -> there is one solution here where all remaining '?'s are '.'s
+        if !states.contains('#') {
+            return 1;
+        } else {
+            // We cannot fit the remaining '#'s, so this is not a solution
+            return 0;
+        }
+    }
+    if states.is_empty() {
+        // We have no more chars in the states. Check that there is only one remaining group and that its length matches our current group_len
+        // Close the last group
+        if counts.len() == 1 && group_len == counts[0] {
+            return 1;
+        }
+        return 0;
+    }
+
+    let rest = &states[1..];
+    // A damaged spring extends the current group
+    let damaged =
+        |memo: &mut CountMemo<'a>| find_good_combinations_count(counts, rest, group_len + 1, memo);
+    let operational = |memo: &mut CountMemo<'a>| {
+        if group_len == 0 {
+            // Not end of group, just move over by 1
+            find_good_combinations_count(counts, rest, 0, memo)
+        } else if counts[0] != group_len {
+            // End of group, which cannot fit the next count
+            0
+        } else {
+            find_good_combinations_count(&counts[1..], rest, 0, memo)
+        }
+    };
+
+    // Handle next char
+    match states.as_bytes()[0] {
+        b'#' => damaged(memo),
+        b'.' => operational(memo),
+        b'?' => {
+            // Memoization, where the search branches
+            if let Some(&v) = memo.get(&(counts, states, group_len)) {
+                return v;
+            }
+            // Sum of the '.' case and the '#' case
+            let d = operational(memo);
+            let h = damaged(memo);
+            trace!(".{} ({:?}) -> {}", rest, counts, d);
+            trace!("#{} ({:?}) -> {}", rest, counts, h);
+            *memo.insert((counts, states, group_len), d + h)
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Parse the rows of springs, like "???.### 1,1,3", into their states and counts
+fn parse_rows(input: &str) -> Result<Vec<(&str, Vec<usize>)>, ParseError> {
+    let mut rows = vec![];
+    for line in input.lines() {
+        let Some((states, counts)) = line.split_once(' ') else {
+            return Err(ParseError::at(input, line, "expected '<springs> <counts>'"));
+        };
+        if let Some(idx) = states.find(|c| !".#?".contains(c)) {
+            let c = states[idx..].chars().next().unwrap();
+            return Err(ParseError::at(
+                input,
+                &states[idx..],
+                format!("unknown spring state '{}'", c),
+            ));
+        }
+        let counts = counts
+            .split(',')
+            .map(|numstr| parse_number(input, numstr))
+            .collect::<Result<_, _>>()?;
+        rows.push((states, counts));
+    }
+    Ok(rows)
+}
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    let mut total_combinations = 0;
+
+    for (states, counts) in parse_rows(input)? {
+        let mut good = find_good_combinations(&counts, states).unwrap_or_default();
+
+        good.sort();
+        good.dedup();
+
+        trace!("{} {:?} -> {}", states, counts, good.len());
+        total_combinations += good.len();
+    }
+    Ok(total_combinations.into())
+}
+
+/// Same as `part1`, counting the arrangements with the memoised search of `part2`
+/// instead of enumerating them.
+pub fn part1_memo(input: &str) -> Result<Answer, ParseError> {
+    let mut total_combinations = 0;
+    let mut stats = Stats::default();
+    for (states, counts) in parse_rows(input)? {
+        let mut memo = Memo::new();
+        total_combinations += find_good_combinations_count(&counts, states, 0, &mut memo);
+        stats += memo.stats();
+    }
+    debug!("memo: {}", stats);
+    stats.record();
+    Ok(total_combinations.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    let mut total_combinations = 0;
+    let mut stats = Stats::default();
+    for (states, counts) in parse_rows(input)? {
+        /// Unfold the input `n_unfoldings` times
+        fn unfold(n_unfoldings: usize, counts: &[usize], states: &str) -> (Vec<usize>, String) {
+            let mut unfolded_counts = vec![];
+            let mut unfolded_states = String::new();
+            for i in 0..n_unfoldings {
+                unfolded_counts.extend(counts);
+
+                unfolded_states.push_str(states);
+                if i != n_unfoldings - 1 {
+                    unfolded_states.push('?');
+                }
+            }
+            (unfolded_counts, unfolded_states)
+        }
+        // Unfolding
+        let (unfold_counts, unfold_states) = unfold(5, &counts, states);
+        let mut memo = Memo::new();
+        let n_sols = find_good_combinations_count(&unfold_counts, &unfold_states, 0, &mut memo);
+        trace!("{} {:?} -> {}", states, counts, n_sols);
+        stats += memo.stats();
+
+        total_combinations += n_sols;
+    }
+    debug!("memo: {}", stats);
+    stats.record();
+    Ok(total_combinations.into())
+}
+
+/// Random records of `size` rows of up to 20 springs, with at least one arrangement
+/// each: the one they were drawn from before about half the springs were hidden.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let mut springs = ".".repeat(rng.range(0..=2));
+        let mut counts = vec![];
+        loop {
+            let count = rng.range(1..=8);
+            let gap = if counts.is_empty() {
+                0
+            } else {
+                rng.range(1..=3)
+            };
+            // The first group always fits
+            if springs.len() + gap + count > 20 {
+                break;
+            }
+            springs += &".".repeat(gap);
+            springs += &"#".repeat(count);
+            counts.push(count.to_string());
+            if rng.chance(0.2) {
+                break;
+            }
+        }
+        springs += &".".repeat(rng.range(0..=2).min(20 - springs.len()));
+        let springs: String = springs
+            .chars()
+            .map(|c| if rng.chance(0.5) { '?' } else { c })
+            .collect();
+        input += &format!("{} {}\n", springs, counts.join(","));
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{part1, part1_memo, part2};
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(21.into()));
+    }
+
+    #[test]
+    fn test_part1_memo_example() {
+        assert_eq!(part1_memo(include_str!("../example.txt")), Ok(21.into()));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(part2(include_str!("../example.txt")), Ok(525152.into()));
+    }
+}
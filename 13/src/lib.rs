@@ -0,0 +1,199 @@
+use aoc_common::random::Rng;
+use aoc_common::{debug, Answer, Grid, ParseError};
+
+fn find_horizontal_reflection(pattern: &Grid<char>) -> usize {
+    // Find a line index such that going down from it or up gives the same line, up to the lower or upper
+    // border of the input.
+    let height = pattern.height();
+    let mut mirror_idx = 0;
+    for i in 1..height {
+        mirror_idx = i;
+        for j in i..height {
+            if pattern.row(j) != pattern.row(i - (j - i) - 1) {
+                mirror_idx = 0;
+            }
+            if i - (j - i) - 1 == 0 {
+                break;
+            }
+        }
+        if mirror_idx == i {
+            break;
+        }
+    }
+    mirror_idx
+}
+
+fn parse_patterns(input: &str) -> Result<Vec<Grid<char>>, ParseError> {
+    let mut patterns = vec![];
+    // Number of lines before the current pattern, to report errors
+    let mut line_offset = 0;
+    for block in input.split("\n\n") {
+        if !block.trim().is_empty() {
+            let pattern = Grid::parse(block, |c| (c == '.' || c == '#').then_some(c))
+                .map_err(|e| e.below(line_offset))?;
+            patterns.push(pattern);
+        }
+        line_offset += block.lines().count() + 1;
+    }
+    Ok(patterns)
+}
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    let input_vec = parse_patterns(input)?;
+    let mut total = 0;
+    for pattern in &input_vec {
+        let mirror_idx = find_horizontal_reflection(pattern);
+        total += 100 * mirror_idx;
+
+        // Repeat with the columns of the input as rows
+        let pattern_rot = pattern.transpose();
+        let mirror_idx_rot = find_horizontal_reflection(&pattern_rot);
+        debug!(
+            "reflection after row {}, column {}",
+            mirror_idx, mirror_idx_rot
+        );
+
+        total += mirror_idx_rot;
+    }
+    Ok(total.into())
+}
+
+fn find_smudged_horizontal_reflection(pattern: &Grid<char>) -> usize {
+    // Find a line index such that going down from it or up gives the same line,
+    // allowing exactly one error in the reflection pattern.
+    let height = pattern.height();
+    let mut mirror_idx = 0;
+    for i in 1..height {
+        mirror_idx = i;
+        let mut errors = 0;
+        for j in i..height {
+            for (c, mirrored) in pattern.row(j).iter().zip(pattern.row(i - (j - i) - 1)) {
+                if c != mirrored {
+                    errors += 1;
+                    if errors > 1 {
+                        mirror_idx = 0;
+                    }
+                }
+            }
+            if i - (j - i) - 1 == 0 {
+                break;
+            }
+        }
+        // Need one error
+        if errors == 0 {
+            mirror_idx = 0;
+        }
+        if mirror_idx == i {
+            break;
+        }
+    }
+    mirror_idx
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    let input_vec = parse_patterns(input)?;
+    let mut total = 0;
+    for pattern in &input_vec {
+        let mirror_idx = find_smudged_horizontal_reflection(pattern);
+        total += 100 * mirror_idx;
+
+        let pattern_rot = pattern.transpose();
+        let mirror_idx_rot = find_smudged_horizontal_reflection(&pattern_rot);
+        debug!(
+            "reflection after row {}, column {}",
+            mirror_idx, mirror_idx_rot
+        );
+        total += mirror_idx_rot;
+    }
+    Ok(total.into())
+}
+
+/// Number of tiles which differ from their reflection, for a mirror between the rows
+/// `line - 1` and `line`.
+fn reflection_errors(pattern: &Grid<char>, line: usize) -> usize {
+    let reflected = line.min(pattern.height() - line);
+    (0..reflected)
+        .map(|k| {
+            let (above, below) = (pattern.row(line - 1 - k), pattern.row(line + k));
+            above.iter().zip(below).filter(|(a, b)| a != b).count()
+        })
+        .sum()
+}
+
+/// Random pattern whose rows reflect perfectly across one line, and with a single
+/// smudge across another one.
+fn random_pattern(rng: &mut Rng) -> Grid<char> {
+    fn root(roots: &mut [usize], row: usize) -> usize {
+        if roots[row] != row {
+            roots[row] = root(roots, roots[row]);
+        }
+        roots[row]
+    }
+
+    loop {
+        let (width, height) = (rng.range(5..=17), rng.range(5..=17));
+        let mirror = rng.range(1..=height - 1);
+        let smudged = rng.range(1..=height - 1);
+        if mirror == smudged {
+            continue;
+        }
+
+        // Rows which must be the same, as sets sharing a root
+        let mut roots: Vec<usize> = (0..height).collect();
+        let smudge = rng.index(smudged.min(height - smudged));
+        for (line, skipped) in [(mirror, None), (smudged, Some(smudge))] {
+            for k in (0..line.min(height - line)).filter(|&k| Some(k) != skipped) {
+                let (a, b) = (root(&mut roots, line - 1 - k), root(&mut roots, line + k));
+                roots[a] = b;
+            }
+        }
+        // The rows of the smudge differ by a single tile
+        let (a, b) = (smudged - 1 - smudge, smudged + smudge);
+        let (a, b) = (root(&mut roots, a), root(&mut roots, b));
+        if a == b {
+            continue;
+        }
+        let mut rows: Vec<Vec<char>> = (0..height)
+            .map(|_| (0..width).map(|_| *rng.choose(&['.', '#'])).collect())
+            .collect();
+        rows[b] = rows[a].clone();
+        let x = rng.index(width);
+        rows[b][x] = if rows[a][x] == '#' { '.' } else { '#' };
+
+        let mut pattern = Grid::from_fn(width, height, |(x, y)| rows[root(&mut roots, y)][x]);
+        if rng.chance(0.5) {
+            pattern = pattern.transpose();
+        }
+        // No other line may reflect the pattern by chance
+        let mut errors = vec![];
+        for pattern in [&pattern, &pattern.transpose()] {
+            errors.extend((1..pattern.height()).map(|line| reflection_errors(pattern, line)));
+        }
+        let count = |n| errors.iter().filter(|&&e| e == n).count();
+        if count(0) == 1 && count(1) == 1 {
+            return pattern;
+        }
+    }
+}
+
+/// Random notes with `size` patterns of ash and rocks, each with one line of
+/// reflection, and a smudge giving another one.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let patterns: Vec<String> = (0..size).map(|_| random_pattern(rng).to_string()).collect();
+    patterns.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(405.into()));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(part2(include_str!("../example.txt")), Ok(400.into()));
+    }
+}
@@ -0,0 +1,298 @@
+use std::collections::HashSet;
+
+use aoc_common::animation::{Cell, Color, Frame, Screen};
+use aoc_common::cycle::advance;
+use aoc_common::metrics;
+use aoc_common::random::Rng;
+use aoc_common::{debug, Answer, Grid, ParseError};
+
+type Platform = Grid<char>;
+
+fn parse_platform(input: &str) -> Result<Platform, ParseError> {
+    Grid::parse(input, |c| "O#.".contains(c).then_some(c))
+}
+
+fn roll_north(map: &mut Platform) {
+    for j in 1..map.height() {
+        for i in 0..map.width() {
+            if map[(i, j)] == 'O' {
+                // Roll rock up to the highest possible (# or O or edge blocks)
+                // Find the closest O or # up from this rock
+                let mut k = j;
+
+                while k != 0 && map[(i, k - 1)] == '.' {
+                    k -= 1;
+                }
+                map[(i, j)] = '.';
+                map[(i, k)] = 'O';
+            }
+        }
+    }
+}
+
+// Move every rock which can roll north by one tile, and return whether any did.
+// Rocks in a column move together, since the ones above move first.
+fn roll_north_one_tile(map: &mut Platform) -> bool {
+    let mut moved = false;
+    for j in 1..map.height() {
+        for i in 0..map.width() {
+            if map[(i, j)] == 'O' && map[(i, j - 1)] == '.' {
+                map[(i, j - 1)] = 'O';
+                map[(i, j)] = '.';
+                moved = true;
+            }
+        }
+    }
+    moved
+}
+
+fn roll_west(map: &mut Platform) {
+    for j in 0..map.height() {
+        for i in 1..map.width() {
+            if map[(i, j)] == 'O' {
+                // Roll rock to the leftmost possible (# or O or edge blocks)
+                let mut k = i;
+
+                while k != 0 && map[(k - 1, j)] == '.' {
+                    k -= 1;
+                }
+                map[(i, j)] = '.';
+                map[(k, j)] = 'O';
+            }
+        }
+    }
+}
+
+fn roll_south(map: &mut Platform) {
+    let height = map.height();
+    for j in (0..height.saturating_sub(1)).rev() {
+        for i in 0..map.width() {
+            if map[(i, j)] == 'O' {
+                // Roll rock down to the southmost possible (# or O or edge blocks)
+                let mut k = j;
+
+                while k != height - 1 && map[(i, k + 1)] == '.' {
+                    k += 1;
+                }
+                map[(i, j)] = '.';
+                map[(i, k)] = 'O';
+            }
+        }
+    }
+}
+
+fn roll_east(map: &mut Platform) {
+    let width = map.width();
+    for j in 0..map.height() {
+        for i in (0..width.saturating_sub(1)).rev() {
+            if map[(i, j)] == 'O' {
+                // Roll rock right to the eastmost possible (# or O or edge blocks)
+                let mut k = i;
+
+                while k != width - 1 && map[(k + 1, j)] == '.' {
+                    k += 1;
+                }
+                map[(i, j)] = '.';
+                map[(k, j)] = 'O';
+            }
+        }
+    }
+}
+
+// Roll north, west, south and east: rotating the platform clockwise after rolling
+// it north brings the next direction to the top, and the last rotation brings it
+// back to its original orientation.
+fn spin_cycle(map: &mut Platform) {
+    for _ in 0..4 {
+        roll_north(map);
+        *map = map.rotate_clockwise();
+    }
+}
+
+// Roll north, west, south and east, each in its own direction, without turning the
+// platform around.
+fn spin_cycle_in_place(map: &mut Platform) {
+    roll_north(map);
+    roll_west(map);
+    roll_south(map);
+    roll_east(map);
+}
+
+fn get_load(map: &Platform) -> usize {
+    map.iter()
+        .filter(|(_, c)| **c == 'O')
+        .map(|((_, j), _)| map.height() - j)
+        .sum()
+}
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    let mut map = parse_platform(input)?;
+    roll_north(&mut map);
+    Ok(get_load(&map).into())
+}
+
+// Move rocks from the lower line to the upper line if possible
+// Returns the upper and lower line
+fn roll_up(upper: &str, lower: &str) -> (String, String) {
+    upper
+        .chars()
+        .zip(lower.chars())
+        .map(|(up, down)| match (up, down) {
+            // Rock in lower row moves up, and frees up the lower row
+            ('.', 'O') => ('O', '.'),
+            // Anything else -> lower row cannot move up
+            tiles => tiles,
+        })
+        .unzip()
+}
+
+fn roll_lines_north(lines: &mut [String]) {
+    for i in (1..lines.len()).rev() {
+        for j in 0..i {
+            let (upper, lower) = roll_up(&lines[j], &lines[j + 1]);
+            lines[j] = upper;
+            lines[j + 1] = lower;
+        }
+    }
+}
+
+fn get_lines_load(lines: &[String]) -> usize {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| (lines.len() - i) * line.matches('O').count())
+        .sum()
+}
+
+/// Same as `part1`, on the lines of the platform as strings: the rocks of each line
+/// roll up to the line above, over and over until they all stopped.
+pub fn part1_lines(input: &str) -> Result<Answer, ParseError> {
+    let platform = parse_platform(input)?;
+    let mut lines: Vec<String> = platform.to_string().lines().map(String::from).collect();
+    roll_lines_north(&mut lines);
+    Ok(get_lines_load(&lines).into())
+}
+
+fn load_after_spin_cycles(input: &str, spin: fn(&mut Platform)) -> Result<usize, ParseError> {
+    let mut map = parse_platform(input)?;
+    debug!("initial platform:\n{}", map);
+
+    // The platform ends up repeating the same spin cycles, so skip over them
+    let n_cycles = 1000000000;
+    if let Some(cycle) = advance(&mut map, n_cycles, spin, Platform::clone) {
+        debug!(
+            "spin cycles repeat every {} cycles after the first {}, the last one is the same as cycle {}",
+            cycle.length,
+            cycle.start,
+            cycle.reduce(n_cycles)
+        );
+        metrics::set("cycle_start", cycle.start as u64);
+        metrics::set("cycle_length", cycle.length as u64);
+    }
+    debug!("final platform:\n{}", map);
+    Ok(get_load(&map))
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    Ok(load_after_spin_cycles(input, spin_cycle)?.into())
+}
+
+/// Same as `part2`, rolling the rocks in each direction on the platform as it is,
+/// instead of turning it to roll them north.
+pub fn part2_in_place(input: &str) -> Result<Answer, ParseError> {
+    Ok(load_after_spin_cycles(input, spin_cycle_in_place)?.into())
+}
+
+fn draw(map: &Platform, caption: String) -> Frame {
+    Frame::from_grid(map, |_, &tile| match tile {
+        'O' => Cell::new(tile, Color::Yellow),
+        '#' => Cell::new(tile, Color::Gray),
+        _ => Cell::new(tile, Color::Default),
+    })
+    .with_caption(caption)
+}
+
+/// Replay the spin cycles of part 2 until the platform is back in a position it was
+/// in before, with the rocks rolling one tile per frame.
+pub fn animate(input: &str, screen: &mut dyn Screen) -> Result<(), ParseError> {
+    let mut map = parse_platform(input)?;
+    let caption = format!("initial platform, load {}", get_load(&map));
+    if !screen.show(&draw(&map, caption)) {
+        return Ok(());
+    }
+
+    let mut seen = HashSet::new();
+    let mut cycle = 1;
+    while seen.insert(map.clone()) {
+        for (turns, direction) in ["north", "west", "south", "east"].iter().enumerate() {
+            while roll_north_one_tile(&mut map) {
+                // Turn the platform back to its original orientation to draw it
+                let mut platform = map.clone();
+                for _ in 0..turns {
+                    platform = platform.rotate_counterclockwise();
+                }
+                let caption = format!(
+                    "spin cycle {}, rolling {}, load {}",
+                    cycle,
+                    direction,
+                    get_load(&platform)
+                );
+                if !screen.show(&draw(&platform, caption)) {
+                    return Ok(());
+                }
+            }
+            map = map.rotate_clockwise();
+        }
+        cycle += 1;
+    }
+    Ok(())
+}
+
+/// Random platform of `size` by `size` tiles, about a fifth of them rounded rocks and
+/// a tenth cube-shaped rocks.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let platform = Grid::from_fn(size, size, |_| match rng.float() {
+        x if x < 0.2 => 'O',
+        x if x < 0.3 => '#',
+        _ => '.',
+    });
+    platform.to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{animate, part1, part1_lines, part2, part2_in_place};
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(136.into()));
+    }
+
+    #[test]
+    fn test_part1_lines_example() {
+        assert_eq!(part1_lines(include_str!("../example.txt")), Ok(136.into()));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(part2(include_str!("../example.txt")), Ok(64.into()));
+    }
+
+    #[test]
+    fn test_part2_in_place_example() {
+        assert_eq!(
+            part2_in_place(include_str!("../example.txt")),
+            Ok(64.into())
+        );
+    }
+
+    #[test]
+    fn test_animate_example() {
+        let mut frames = vec![];
+        animate(include_str!("../example.txt"), &mut frames).unwrap();
+        // The platform after 10 spin cycles is the same as after 3
+        let last = &frames.last().unwrap().caption;
+        assert!(last.starts_with("spin cycle 10, "), "{}", last);
+        assert!(last.ends_with(", load 69"), "{}", last);
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::{HashMap, VecDeque};
+
+use aoc_common::random::Rng;
+use aoc_common::{debug, parse_number, Answer, ParseError};
+
+fn hash(s: &str) -> usize {
+    s.chars()
+        .filter(|c| *c != '\n')
+        .fold(0, |acc, c| (acc + (c as usize)) * 17 % 256)
+}
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    let mut total = 0;
+    for instruction in input.split(',') {
+        total += hash(instruction);
+    }
+    Ok(total.into())
+}
+
+type Lens = (String, usize);
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    let steps: Vec<_> = input.split(',').map(|step| step.trim_end()).collect();
+
+    let mut boxes: HashMap<usize, VecDeque<Lens>> = HashMap::new();
+
+    for step in steps {
+        if step.contains('-') {
+            // Remove step
+            let label = step.split('-').next().unwrap();
+            let box_id = hash(label);
+
+            if let Some(lenses) = boxes.get_mut(&box_id) {
+                if let Some(lens_idx) = lenses.iter().position(|x| x.0 == label) {
+                    lenses.remove(lens_idx);
+                }
+            }
+        } else if step.contains('=') {
+            // Assign step
+            let mut op = step.split('=');
+            let label = op.next().unwrap();
+            let focal: usize = parse_number(input, op.next().unwrap())?;
+
+            let box_id = hash(label);
+            let lenses = boxes.entry(box_id).or_default();
+
+            if let Some(old_lens) = lenses.iter_mut().find(|x| x.0 == label) {
+                old_lens.1 = focal;
+            } else {
+                lenses.push_back((label.to_string(), focal));
+            }
+        } else {
+            return Err(ParseError::at(
+                input,
+                step,
+                "expected '<label>-' or '<label>=<focal length>'",
+            ));
+        }
+    }
+    debug!("boxes: {:?}", boxes);
+    Ok(get_focusing_power(&boxes).into())
+}
+
+fn get_focusing_power(boxes: &HashMap<usize, VecDeque<Lens>>) -> usize {
+    let mut total = 0;
+    for (box_id, lenses) in boxes {
+        for (lens_idx, (_, focal)) in lenses.iter().enumerate() {
+            total += (1 + *box_id) * (lens_idx + 1) * focal;
+        }
+    }
+    total
+}
+
+/// Random initialization sequence of `size` steps, putting in and taking out lenses
+/// with a quarter as many labels, so that the same lenses come back again and again.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let labels: Vec<String> = (0..size / 4 + 1)
+        .map(|_| {
+            (0..rng.range(2..=6))
+                .map(|_| char::from(rng.range(b'a'..=b'z')))
+                .collect()
+        })
+        .collect();
+    let steps: Vec<String> = (0..size)
+        .map(|_| {
+            let label = rng.choose(&labels);
+            if rng.chance(0.7) {
+                format!("{}={}", label, rng.range(1..=9))
+            } else {
+                format!("{}-", label)
+            }
+        })
+        .collect();
+    steps.join(",") + "\n"
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{hash, part1, part2};
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash("HASH"), 52);
+    }
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(1320.into()));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(part2(include_str!("../example.txt")), Ok(145.into()));
+    }
+}
@@ -0,0 +1,319 @@
+use std::{collections::HashSet, sync::mpsc, thread};
+
+use aoc_common::animation::{Cell, Color, Frame, Screen};
+use aoc_common::export::{Picture, Rgb};
+use aoc_common::geom::Direction;
+use aoc_common::grid::Position;
+use aoc_common::log::{self, Level};
+use aoc_common::metrics;
+use aoc_common::random::Rng;
+use aoc_common::{debug, trace, Answer, Grid, ParseError};
+
+type Map = Grid<char>;
+
+fn parse_map(input: &str) -> Result<Map, ParseError> {
+    Grid::parse(input, |c| ".|-/\\".contains(c).then_some(c))
+}
+
+// Directions in which a beam leaves a tile, depending on the direction it came in:
+// one, or two when it is split
+fn deflect(tile: char, direction: Direction) -> &'static [Direction] {
+    use Direction::*;
+    match (tile, direction) {
+        // Empty space, or the pointy end of a splitter: go straight on
+        ('.', _) | ('|', Up | Down) | ('-', Left | Right) => match direction {
+            Up => &[Up],
+            Right => &[Right],
+            Down => &[Down],
+            Left => &[Left],
+        },
+        // Flat side of a splitter
+        ('|', Left | Right) => &[Up, Down],
+        ('-', Up | Down) => &[Left, Right],
+        // Direction change
+        ('\\', Right) => &[Down],
+        ('\\', Left) => &[Up],
+        ('\\', Down) => &[Right],
+        ('\\', Up) => &[Left],
+        ('/', Right) => &[Up],
+        ('/', Left) => &[Down],
+        ('/', Down) => &[Left],
+        ('/', Up) => &[Right],
+        _ => panic!(),
+    }
+}
+
+// Returns the list of tiles energized by this beam and any splits
+fn trace_beam(
+    map: &Map,
+    start_pos: Position,
+    start_direction: Direction,
+    visited: &mut Vec<(Position, Direction)>,
+) -> Vec<Position> {
+    let mut energized = vec![];
+    let mut position = start_pos;
+    let mut direction = start_direction;
+    loop {
+        visited.push((position, direction));
+
+        trace!("{:?} {:?}", position, direction);
+        // Add this tile to the energized ones
+        energized.push(position);
+        // Determine where to go based on the current tile and direction
+        match deflect(map[position], direction) {
+            &[next] => direction = next,
+            splits => {
+                // Shoot beams both ways from here, and end this beam
+                for &split in splits {
+                    energized.extend(trace_beam(map, position, split, visited));
+                }
+                break;
+            }
+        }
+
+        // Keep going, unless we have reached an edge
+        match map.step(position, direction) {
+            Some(next) => position = next,
+            None => break,
+        }
+
+        if visited.contains(&(position, direction)) {
+            break;
+        }
+    }
+    energized
+}
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    let map = parse_map(input)?;
+    // Compute the result of tracing the beam
+    let mut visited = vec![];
+    let mut energized = trace_beam(&map, (0, 0), Direction::Right, &mut visited);
+    metrics::add("visited_pairs", visited.len() as u64);
+
+    // Pretty print the map and the energized tiles
+    if log::enabled(Level::Debug) {
+        let tiles = Grid::from_fn(map.width(), map.height(), |position| {
+            if energized.contains(&position) {
+                '#'
+            } else {
+                '.'
+            }
+        });
+        debug!("contraption:\n{}", map.to_string().trim_end());
+        debug!("energized tiles:\n{}", tiles.to_string().trim_end());
+    }
+
+    energized.sort();
+    energized.dedup();
+    Ok(energized.len().into())
+}
+
+/// Draw the tiles energized in part 1 over the contraption, with a square per tile.
+pub fn render(input: &str) -> Result<Picture, ParseError> {
+    let map = parse_map(input)?;
+    let energized: HashSet<_> = trace_beam(&map, (0, 0), Direction::Right, &mut vec![])
+        .into_iter()
+        .collect();
+    let image = Grid::from_fn(map.width(), map.height(), |position| {
+        match (map[position], energized.contains(&position)) {
+            ('.', false) => Rgb::new(20, 20, 30),
+            ('.', true) => Rgb::new(255, 210, 60),
+            // Mirrors and splitters
+            (_, false) => Rgb::new(110, 110, 130),
+            (_, true) => Rgb::new(255, 120, 30),
+        }
+    });
+    Ok(Picture::Raster { image, scale: 6 })
+}
+
+/// Replay the beam of part 1 spreading through the contraption, one tile per frame.
+pub fn animate(input: &str, screen: &mut dyn Screen) -> Result<(), ParseError> {
+    let map = parse_map(input)?;
+    let mut energized = Grid::new(map.width(), map.height(), false);
+    let mut beams = vec![((0, 0), Direction::Right)];
+    let mut visited: HashSet<_> = beams.iter().copied().collect();
+    let mut step = 0;
+    while !beams.is_empty() {
+        for &(position, _) in &beams {
+            energized[position] = true;
+        }
+        let count = energized.iter().filter(|(_, &tile)| tile).count();
+        let frame = Frame::from_grid(&map, |position, &tile| {
+            if let Some((_, direction)) = beams.iter().find(|(beam, _)| *beam == position) {
+                Cell::new(direction.arrow(), Color::Red)
+            } else if energized[position] {
+                Cell::new(if tile == '.' { '#' } else { tile }, Color::Yellow)
+            } else if tile == '.' {
+                Cell::new(tile, Color::Gray)
+            } else {
+                Cell::new(tile, Color::Cyan)
+            }
+        });
+        let caption = format!(
+            "step {}: {} beam(s), {} tiles energized",
+            step,
+            beams.len(),
+            count
+        );
+        if !screen.show(&frame.with_caption(caption)) {
+            break;
+        }
+
+        // Move every beam one tile, dropping the ones leaving the contraption or
+        // following another beam
+        beams = beams
+            .into_iter()
+            .flat_map(|(position, direction)| {
+                let map = &map;
+                deflect(map[position], direction)
+                    .iter()
+                    .filter_map(move |&next| Some((map.step(position, next)?, next)))
+            })
+            .filter(|&beam| visited.insert(beam))
+            .collect();
+        step += 1;
+    }
+    Ok(())
+}
+
+/// Number of tiles energized by a beam, and of (position, direction) pairs it visited
+fn count_energized(map: &Map, position: Position, direction: Direction) -> (usize, usize) {
+    let mut visited = vec![];
+    let mut energized = trace_beam(map, position, direction, &mut visited);
+    energized.sort();
+    energized.dedup();
+    (energized.len(), visited.len())
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    let map = parse_map(input)?;
+    let (width, height) = (map.width(), map.height());
+    let mut max_energized = 0;
+    let (tx, rx) = mpsc::channel();
+    let mut handles = vec![];
+    for j in 0..height {
+        // Try every starting point from the left
+        let tx1 = tx.clone();
+        let map1 = map.clone();
+        handles.push(thread::spawn(move || {
+            tx1.send(count_energized(&map1, (0, j), Direction::Right))
+                .unwrap();
+        }));
+
+        // Try every starting point from the right
+        let tx1 = tx.clone();
+        let map1 = map.clone();
+        handles.push(thread::spawn(move || {
+            tx1.send(count_energized(&map1, (width - 1, j), Direction::Left))
+                .unwrap();
+        }));
+    }
+
+    for i in 0..width {
+        // Try every starting point from the top
+        let tx1 = tx.clone();
+        let map1 = map.clone();
+        handles.push(thread::spawn(move || {
+            tx1.send(count_energized(&map1, (i, 0), Direction::Down))
+                .unwrap();
+        }));
+
+        // Try every starting point from the bottom
+        let tx1 = tx.clone();
+        let map1 = map.clone();
+        handles.push(thread::spawn(move || {
+            tx1.send(count_energized(&map1, (i, height - 1), Direction::Up))
+                .unwrap();
+        }));
+    }
+    // Unused transmitter
+    drop(tx);
+    // The metrics of the threads are added up here
+    let mut visited_pairs = 0;
+    for (received, visited) in rx {
+        if received > max_energized {
+            max_energized = received;
+        }
+        visited_pairs += visited;
+    }
+    // A thread which panicked sent nothing, so pass its panic on rather than the max
+    // of the others
+    for handle in handles {
+        if let Err(panic) = handle.join() {
+            std::panic::resume_unwind(panic);
+        }
+    }
+    metrics::add("visited_pairs", visited_pairs as u64);
+
+    Ok(max_energized.into())
+}
+
+/// Same as `part2`, trying every starting point one after the other on the
+/// current thread.
+pub fn part2_single_thread(input: &str) -> Result<Answer, ParseError> {
+    let map = parse_map(input)?;
+    let mut starts = vec![];
+    for j in 0..map.height() {
+        starts.push(((0, j), Direction::Right));
+        starts.push(((map.width() - 1, j), Direction::Left));
+    }
+    for i in 0..map.width() {
+        starts.push(((i, 0), Direction::Down));
+        starts.push(((i, map.height() - 1), Direction::Up));
+    }
+
+    let mut max_energized = 0;
+    let mut visited_pairs = 0;
+    for (position, direction) in starts {
+        let (energized, visited) = count_energized(&map, position, direction);
+        max_energized = max_energized.max(energized);
+        visited_pairs += visited;
+    }
+    metrics::add("visited_pairs", visited_pairs as u64);
+    Ok(max_energized.into())
+}
+
+/// Random contraption of `size` by `size` tiles, about one in ten of them a mirror or
+/// a splitter.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let map = Grid::from_fn(size, size, |_| {
+        if rng.chance(0.1) {
+            *rng.choose(&['/', '\\', '|', '-'])
+        } else {
+            '.'
+        }
+    });
+    map.to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{animate, part1, part2, part2_single_thread};
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(46.into()));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(part2(include_str!("../example.txt")), Ok(51.into()));
+    }
+
+    #[test]
+    fn test_animate_example() {
+        let mut frames = vec![];
+        animate(include_str!("../example.txt"), &mut frames).unwrap();
+        let last = &frames.last().unwrap().caption;
+        assert!(last.ends_with(" 46 tiles energized"), "{}", last);
+    }
+
+    #[test]
+    fn test_part2_single_thread_example() {
+        assert_eq!(
+            part2_single_thread(include_str!("../example.txt")),
+            Ok(51.into())
+        );
+    }
+}
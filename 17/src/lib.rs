@@ -0,0 +1,186 @@
+use aoc_common::export::{Picture, Rgb};
+use aoc_common::geom::Direction;
+use aoc_common::grid::Position;
+use aoc_common::metrics;
+use aoc_common::random::Rng;
+use aoc_common::search::{dijkstra, SearchResult};
+use aoc_common::{debug, Answer, Grid, ParseError};
+
+/// Heat loss of each city block
+type Map = Grid<u8>;
+
+fn parse_map(input: &str) -> Result<Map, ParseError> {
+    Grid::parse(input, |c| c.to_digit(10).map(|digit| digit as u8))
+}
+
+/// Draw the path over the map, one line per row of the map
+fn draw_path(map: &Map, path: &[State]) -> String {
+    let mut drawing = String::new();
+    for j in 0..map.height() {
+        drawing.push('\n');
+        for i in 0..map.width() {
+            if (i, j) == (0, 0) {
+                drawing.push('X');
+            } else if let Some(x) = path.iter().find(|x| x.pos == (i, j)) {
+                drawing.push(x.direction.arrow())
+            } else {
+                drawing += &map[(i, j)].to_string();
+            }
+        }
+    }
+    drawing
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+struct State {
+    pos: Position,
+    direction: Direction,
+    consecutive: usize,
+}
+
+/// Limits on the number of consecutive steps a crucible makes in the same direction
+struct Crucible {
+    /// Steps before it can turn or stop
+    min_straight: usize,
+    /// Steps before it must turn
+    max_straight: usize,
+}
+
+const CRUCIBLE: Crucible = Crucible {
+    min_straight: 1,
+    max_straight: 3,
+};
+
+const ULTRA_CRUCIBLE: Crucible = Crucible {
+    min_straight: 4,
+    max_straight: 10,
+};
+
+impl Crucible {
+    /// States reachable in one step, with the heat lost entering the new city block.
+    /// The crucible cannot go backward, and can start in any direction.
+    fn moves(&self, map: &Map, node: &State) -> Vec<(State, u32)> {
+        let mut moves = vec![];
+        for dir in Direction::ALL {
+            let Some(tgt) = map.step(node.pos, dir) else {
+                continue;
+            };
+            let mut consec = 1;
+            // Do not apply to first step
+            if node.consecutive > 0 {
+                // Cannot go backward
+                if dir == node.direction.reverse() {
+                    continue;
+                }
+                // Have to keep going straight for the minimum number of steps
+                if node.consecutive < self.min_straight && dir != node.direction {
+                    continue;
+                }
+                consec = if dir == node.direction {
+                    node.consecutive + 1
+                } else {
+                    1
+                };
+
+                // Cannot go further in the same direction
+                if consec > self.max_straight {
+                    continue;
+                }
+            }
+
+            let state = State {
+                pos: tgt,
+                consecutive: consec,
+                direction: dir,
+            };
+            moves.push((state, map[tgt] as u32));
+        }
+        moves
+    }
+
+    /// Path with the least heat loss from the top left to the bottom right city block.
+    /// Uses "stateful" steps in the 2D grid, with each having a direction and remembering
+    /// the number of consecutive steps in that direction.
+    fn best_path(&self, map: &Map) -> Result<SearchResult<State, u32>, ParseError> {
+        let start = State {
+            pos: (0, 0),
+            direction: Direction::Right,
+            consecutive: 0,
+        };
+        let end = (map.width() - 1, map.height() - 1);
+        // The end condition must take into account the minimum consecutive steps
+        let result = dijkstra(
+            start,
+            |node| self.moves(map, node),
+            |node| node.pos == end && node.consecutive >= self.min_straight,
+        )
+        .ok_or_else(|| ParseError::new(1, 1, "no path to the bottom right city block"))?;
+        Ok(result)
+    }
+
+    fn least_heat_loss(&self, map: &Map) -> Result<u32, ParseError> {
+        let result = self.best_path(map)?;
+        debug!(
+            "path with heat loss {} after expanding {} states:{}",
+            result.cost,
+            result.expanded,
+            draw_path(map, &result.path)
+        );
+        metrics::add("states_expanded", result.expanded as u64);
+        Ok(result.cost)
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    let map = parse_map(input)?;
+    Ok(CRUCIBLE.least_heat_loss(&map)?.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    let map = parse_map(input)?;
+    Ok(ULTRA_CRUCIBLE.least_heat_loss(&map)?.into())
+}
+
+/// Draw the path of the crucible of part 1 over the map, with a square per city block
+/// shaded by its heat loss. The path goes from green to red as the crucible loses heat.
+pub fn render(input: &str) -> Result<Picture, ParseError> {
+    const START: Rgb = Rgb::new(40, 200, 60);
+    const END: Rgb = Rgb::new(230, 30, 30);
+
+    let map = parse_map(input)?;
+    let result = CRUCIBLE.best_path(&map)?;
+    let mut image = map.map(|&heat| Rgb::WHITE.mix(Rgb::BLACK, heat as f64 / 10.0));
+    let mut lost = 0;
+    for (idx, state) in result.path.iter().enumerate() {
+        // No heat is lost in the starting block
+        if idx > 0 {
+            lost += map[state.pos] as u32;
+        }
+        image[state.pos] = START.mix(END, lost as f64 / result.cost.max(1) as f64);
+    }
+    Ok(Picture::Raster { image, scale: 6 })
+}
+
+/// Random map of `size` by `size` city blocks, or 5 by 5 for smaller sizes, which is
+/// the smallest map ultra crucibles can cross.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(5);
+    let map: Map = Grid::from_fn(size, size, |_| rng.range(1..=9));
+    map.to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(102.into()));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(part2(include_str!("../example.txt")), Ok(94.into()));
+        assert_eq!(part2(include_str!("../example2.txt")), Ok(71.into()));
+    }
+}
@@ -0,0 +1,453 @@
+use std::collections::{BTreeSet, HashMap};
+
+use aoc_common::export::{Picture, Rgb, Svg};
+use aoc_common::geom::{Direction, Point};
+use aoc_common::interval::{Interval, IntervalSet};
+use aoc_common::log::{self, Level};
+use aoc_common::random::{self, Rng};
+use aoc_common::{debug, parse_number, trace, Answer, ParseError};
+
+type Position = Point<i64>;
+
+/// One step of the dig plan: a direction and a distance
+struct DigStep {
+    direction: Direction,
+    distance: i64,
+}
+
+/// The 6 hex digits of a color like "(#70c710)"
+fn parse_color<'a>(input: &str, color: &'a str) -> Result<&'a str, ParseError> {
+    color
+        .strip_prefix("(#")
+        .and_then(|c| c.strip_suffix(')'))
+        .filter(|c| c.len() == 6 && c.chars().all(|c| c.is_ascii_hexdigit()))
+        .ok_or_else(|| ParseError::at(input, color, "expected a color like '(#70c710)'"))
+}
+
+/// Parse the dig plan, with lines like "R 6 (#70c710)". In part 2, the real
+/// step is hidden in the color code: 5 hex digits of distance, then 1 of direction.
+fn parse_plan(input: &str, from_color: bool) -> Result<Vec<DigStep>, ParseError> {
+    let mut steps = vec![];
+    for instruction in input.lines() {
+        let [direction, distance, color] = instruction.split_whitespace().collect::<Vec<_>>()[..]
+        else {
+            return Err(ParseError::at(
+                input,
+                instruction,
+                "expected '<direction> <distance> (#<color>)'",
+            ));
+        };
+        let step = if !from_color {
+            DigStep {
+                direction: match direction {
+                    "R" => Direction::Right,
+                    "D" => Direction::Down,
+                    "L" => Direction::Left,
+                    "U" => Direction::Up,
+                    _ => {
+                        return Err(ParseError::at(
+                            input,
+                            direction,
+                            format!("unknown direction '{}'", direction),
+                        ))
+                    }
+                },
+                distance: parse_number(input, distance)?,
+            }
+        } else {
+            let hex = parse_color(input, color)?;
+            DigStep {
+                direction: match &hex[5..] {
+                    "0" => Direction::Right,
+                    "1" => Direction::Down,
+                    "2" => Direction::Left,
+                    "3" => Direction::Up,
+                    _ => {
+                        return Err(ParseError::at(
+                            input,
+                            &hex[5..],
+                            "expected a direction from 0 to 3",
+                        ))
+                    }
+                },
+                distance: i64::from_str_radix(&hex[..5], 16).map_err(|_| {
+                    ParseError::at(input, &hex[..5], "expected a distance of 5 hex digits")
+                })?,
+            }
+        };
+        steps.push(step);
+    }
+    if steps.is_empty() {
+        return Err(ParseError::new(1, 1, "empty dig plan"));
+    }
+    Ok(steps)
+}
+
+/// The smallest and largest x, then y, of the points, which start at the origin.
+fn bounds(points: &[Position]) -> (i64, i64, i64, i64) {
+    points
+        .iter()
+        .fold((0, 0, 0, 0), |(min_x, max_x, min_y, max_y), p| {
+            (
+                min_x.min(p.x),
+                max_x.max(p.x),
+                min_y.min(p.y),
+                max_y.max(p.y),
+            )
+        })
+}
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    let mut path: Vec<Position> = vec![];
+    path.push(Point::new(0, 0));
+
+    for DigStep {
+        direction,
+        distance,
+    } in parse_plan(input, false)?
+    {
+        for _ in 0..distance {
+            let last = *path.last().unwrap();
+            path.push(last + direction.vector());
+        }
+    }
+
+    let (min_x, max_x, min_y, max_y) = bounds(&path);
+
+    // show path
+    if log::enabled(Level::Debug) {
+        let mut trench = String::new();
+        for j in min_y..=max_y {
+            trench.push('\n');
+            for i in min_x..=max_x {
+                trench.push(if path.contains(&Point::new(i, j)) {
+                    '#'
+                } else {
+                    '.'
+                });
+            }
+        }
+        debug!("trench:{}", trench);
+    }
+
+    // fill path
+    let filled_count = get_filled_count(&path, min_x, max_x, min_y, max_y);
+    Ok(filled_count.into())
+}
+
+/// Draw the lagoon of part 1, with each edge of the trench in the color of its step
+/// of the dig plan.
+pub fn render(input: &str) -> Result<Picture, ParseError> {
+    let steps = parse_plan(input, false)?;
+    let mut colors = vec![];
+    for line in input.lines() {
+        // parse_plan checked that every line has a color
+        let color = line.split_whitespace().nth(2).unwrap_or(line);
+        let hex = parse_color(input, color)?;
+        let rgb = Rgb::parse_hex(&format!("#{}", hex));
+        colors.push(rgb.ok_or_else(|| ParseError::at(input, color, "invalid color"))?);
+    }
+
+    // Corners of the trench, at the center of their cubes
+    let mut corner = Point::new(0, 0);
+    let mut corners = vec![corner];
+    for step in &steps {
+        corner += step.direction.vector() * step.distance;
+        corners.push(corner);
+    }
+    let centers: Vec<_> = corners
+        .iter()
+        .map(|p| (p.x as f64 + 0.5, p.y as f64 + 0.5))
+        .collect();
+
+    let (min_x, max_x, min_y, max_y) = bounds(&corners);
+    let (width, height) = (max_x - min_x + 1, max_y - min_y + 1);
+    // Aim for an image of around 800 pixels
+    let scale = (800.0 / width.max(height) as f64).max(1.0);
+    let mut svg =
+        Svg::new(min_x as f64, min_y as f64, width as f64, height as f64).with_scale(scale);
+
+    svg.polygon(&centers, Rgb::new(30, 40, 60));
+    for (edge, color) in centers.windows(2).zip(colors) {
+        svg.line(edge[0], edge[1], color, 1.0);
+    }
+    Ok(Picture::Vector(svg))
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    // Reinterpret input: color codes are hex direction + distance.
+    // Efficiency:
+    // Instead of considering each step in the path, consider edges of the pool
+    // Define edge intersection method so we can more quickly determine if a point is inside the polygon
+    let mut edges: Vec<Edge> = vec![];
+    let mut cur = Point::new(0, 0);
+    for DigStep {
+        direction,
+        distance,
+    } in parse_plan(input, true)?
+    {
+        let next = cur + direction.vector() * distance;
+        edges.push(Edge::new(cur.x, next.x, cur.y, next.y));
+        cur = next;
+    }
+
+    let min_y = edges
+        .iter()
+        .map(|x| x.y0.min(x.y1))
+        .min_by_key(|x| *x)
+        .unwrap();
+    let max_y = edges
+        .iter()
+        .map(|x| x.y0.max(x.y1))
+        .max_by_key(|x| *x)
+        .unwrap();
+
+    // Sort from left to right to simplify algorithm
+    edges.sort_by_key(|a| a.x0);
+
+    let mut area_inside: u64 = 0;
+    let mut previous_line_inside = IntervalSet::new();
+    for j in min_y..=max_y {
+        let mut this_line_inside = IntervalSet::new();
+        // Cast a ray from the left and find all intersections with vertical edges
+        let ray = ((1, 0), j);
+        let mut inside = false;
+        let mut start = 0;
+        for edge in &edges {
+            match edge.intersects(ray) {
+                Intersection::Some(x) => {
+                    if !inside {
+                        start = x;
+                        inside = true;
+                    } else {
+                        this_line_inside.insert(Interval::new(start, x + 1));
+                        inside = false;
+                    }
+                }
+                Intersection::Collinear(x0, x1) => {
+                    if inside {
+                        // We are inside and we reach a collinear
+                        // Determine if we are inside or outside after the segment
+                        if previous_line_inside.contains(x1 + 1) {
+                            // We are still INSIDE after the collinear
+                            // DO NOTHING
+                        } else {
+                            // We are OUTSIDE after the collinear
+                            inside = false;
+                            this_line_inside.insert(Interval::new(start, x1 + 1));
+                        }
+                    } else {
+                        // We are outside and we reach a collinear
+                        // Determine if the tile after the segment is still inside or outside
+                        if j == min_y {
+                            // Outside
+                            this_line_inside.insert(Interval::new(x0, x1 + 1));
+                        } else if previous_line_inside.contains(x1 + 1) {
+                            // We are still INSIDE after the collinear
+                            inside = true;
+                            start = x0;
+                        } else {
+                            // We are OUTSIDE after the collinear
+                            this_line_inside.insert(Interval::new(x0, x1 + 1));
+                        }
+                    }
+                }
+                Intersection::None => {}
+            }
+        }
+
+        area_inside += this_line_inside.len() as u64;
+        trace!("line {}: inside {:?}", j, this_line_inside.intervals());
+
+        previous_line_inside = this_line_inside;
+    }
+    Ok(area_inside.into())
+}
+
+#[derive(Debug)]
+struct Edge {
+    x0: i64,
+    x1: i64,
+    y0: i64,
+    y1: i64,
+}
+
+enum Intersection {
+    Some(i64),
+    None,
+    Collinear(i64, i64),
+}
+
+impl Edge {
+    fn new(x0: i64, x1: i64, y0: i64, y1: i64) -> Self {
+        Self { x0, x1, y0, y1 }
+    }
+
+    /// ray is ((direction_x, directon_y), offset_x/y)
+    /// returns the position of intersection, or None
+    fn intersects(&self, ray: ((i64, i64), i64)) -> Intersection {
+        match ray.0 {
+            (1, 0) | (-1, 0) => {
+                let min_y = self.y0.min(self.y1);
+                let max_y = self.y0.max(self.y1);
+                if self.y0 == self.y1 && self.y0 == ray.1 {
+                    Intersection::Collinear(self.x0.min(self.x1), self.x0.max(self.x1))
+                } else if (min_y + 1..max_y).contains(&ray.1) {
+                    // Do not consider collinear as intersection
+                    Intersection::Some(self.x0)
+                } else {
+                    Intersection::None
+                }
+            }
+            (0, -1) | (0, 1) => {
+                let min_x = self.x0.min(self.x1);
+                let max_x = self.x0.max(self.x1);
+                if (min_x..=max_x).contains(&ray.1) && self.x0 != self.x1 {
+                    Intersection::Some(self.y0)
+                } else {
+                    Intersection::None
+                }
+            }
+            _ => panic!(),
+        }
+    }
+}
+
+// mod test {
+//     use crate::Edge;
+
+//     #[test]
+//     fn test_intersect() {
+//         let edge = Edge::new(0, 20, 5, 5);
+//         assert_eq!(edge.intersects(((1, 0), 5)), None);
+//         assert_eq!(edge.intersects(((0, 1), 5)), Some(5));
+//         assert_eq!(edge.intersects(((0, 1), 20)), Some(5));
+//         assert_eq!(edge.intersects(((0, 1), 21)), None);
+//         assert_eq!(edge.intersects(((0, -1), -1)), None);
+//         let edge = Edge::new(10, 10, -25, 75);
+//         assert_eq!(edge.intersects(((1, 0), -26)), None);
+//         assert_eq!(edge.intersects(((1, 0), -25)), Some(10));
+//         assert_eq!(edge.intersects(((1, 0), 5)), Some(10));
+//         assert_eq!(edge.intersects(((1, 0), 10)), Some(10));
+//         assert_eq!(edge.intersects(((-1, 0), 10)), Some(10));
+//         assert_eq!(edge.intersects(((0, 1), 21)), None);
+//         assert_eq!(edge.intersects(((0, -1), -5)), None);
+//     }
+// }
+
+fn get_filled_count(path: &[Position], min_x: i64, max_x: i64, min_y: i64, max_y: i64) -> i64 {
+    let mut filled_count = 0;
+    let mut previous_line_inside = HashMap::new();
+    for j in min_y..=max_y {
+        let mut last = false;
+        let mut inside = 0;
+        let mut on_edge = false;
+        let mut this_line_inside = HashMap::new();
+
+        for i in min_x..=max_x {
+            let cur = path.contains(&Point::new(i, j));
+            if cur != last {
+                inside += 1;
+                if on_edge {
+                    if j == min_y {
+                        // We are outside
+                        inside = 0;
+                    } else {
+                        // Use previous line to determine if we are inside after the edge
+                        if let Some(v) = previous_line_inside.get(&i) {
+                            inside = *v;
+                        } else {
+                            inside = 0;
+                        }
+                    }
+                    on_edge = false;
+                }
+            } else if cur && last {
+                // We are on an edge
+                on_edge = true;
+            }
+            last = cur;
+            trace!("{} {} -> {}", i, j, inside);
+
+            if inside % 4 != 0 || cur {
+                this_line_inside.insert(i, inside);
+                filled_count += 1;
+            }
+        }
+        previous_line_inside = this_line_inside;
+    }
+    filled_count
+}
+
+/// Spread the `values` apart, keeping them in order, with gaps of up to `max_gap`
+/// between them.
+fn stretch(rng: &mut Rng, values: BTreeSet<usize>, max_gap: i32) -> HashMap<usize, i32> {
+    let mut stretched = 0;
+    values
+        .into_iter()
+        .map(|value| {
+            stretched += rng.range(1..=max_gap);
+            (value, stretched)
+        })
+        .collect()
+}
+
+/// Random dig plan around a blob on a `size` by `size` grid. Both parts dig the same
+/// shape, with the edges stretched to a few meters in part 1, and to up to a million
+/// meters in total in part 2: the dig plans never cross themselves.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let outline = random::random_loop(rng, size.max(2), size.max(2));
+    let n = outline.len();
+    let corners: Vec<_> = (0..n)
+        .map(|i| (outline[(i + n - 1) % n], outline[i], outline[(i + 1) % n]))
+        .filter(|(before, _, after)| before.0 != after.0 && before.1 != after.1)
+        .map(|(_, corner, _)| corner)
+        .collect();
+
+    let xs: BTreeSet<_> = corners.iter().map(|corner| corner.0).collect();
+    let ys: BTreeSet<_> = corners.iter().map(|corner| corner.1).collect();
+    let max_gap = (1_000_000 / xs.len().max(ys.len())) as i32;
+    let small = [stretch(rng, xs.clone(), 6), stretch(rng, ys.clone(), 6)];
+    let large = [stretch(rng, xs, max_gap), stretch(rng, ys, max_gap)];
+
+    let mut input = String::new();
+    for (i, &(x, y)) in corners.iter().enumerate() {
+        let (next_x, next_y) = corners[(i + 1) % corners.len()];
+        let step =
+            |[xs, ys]: &[HashMap<usize, i32>; 2]| (xs[&next_x] - xs[&x], ys[&next_y] - ys[&y]);
+        let (dx, dy) = step(&small);
+        let (direction, code) = match (dx.signum(), dy.signum()) {
+            (1, _) => ("R", 0),
+            (_, 1) => ("D", 1),
+            (-1, _) => ("L", 2),
+            _ => ("U", 3),
+        };
+        let (large_dx, large_dy) = step(&large);
+        input += &format!(
+            "{} {} (#{:05x}{})\n",
+            direction,
+            dx.abs() + dy.abs(),
+            large_dx.abs() + large_dy.abs(),
+            code
+        );
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(62.into()));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(
+            part2(include_str!("../example.txt")),
+            Ok(952408144115_i64.into())
+        );
+    }
+}
@@ -0,0 +1,680 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::interval::{Cuboid, Interval};
+use aoc_common::random::Rng;
+use aoc_common::repl::{wrong_arguments, Command, Session};
+use aoc_common::{parse_number, trace, Answer, ParseError};
+
+/// Ratings go from 1 to 4000
+const RATINGS: Interval<u64> = Interval::new(1, 4001);
+
+/// Combinations of ratings, with an axis for each category in the order of `Operand`
+type Parts = Cuboid<u64, 4>;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Target {
+    GoTo(String),
+    Accept,
+    Reject,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Operator {
+    Gt,
+    Lt,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Operand {
+    X,
+    M,
+    A,
+    S,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Condition {
+    operand: Operand,
+    operator: Operator,
+    value: u32,
+}
+
+impl Condition {
+    /// Ratings which pass the condition
+    fn passing(&self) -> Interval<u64> {
+        let value = self.value as u64;
+        match self.operator {
+            Operator::Gt => Interval::new(value + 1, RATINGS.end),
+            Operator::Lt => Interval::new(RATINGS.start, value),
+        }
+    }
+
+    /// Ratings which fail the condition, and go on to the next rule
+    fn failing(&self) -> Interval<u64> {
+        let value = self.value as u64;
+        match self.operator {
+            Operator::Gt => Interval::new(RATINGS.start, value + 1),
+            Operator::Lt => Interval::new(value, RATINGS.end),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Rule {
+    condition: Option<Condition>,
+    target: Target,
+}
+
+#[derive(Debug)]
+struct Workflow {
+    rules: Vec<Rule>,
+}
+
+impl Workflow {
+    fn new() -> Self {
+        Self { rules: vec![] }
+    }
+}
+
+fn parse_condition(input: &str, cond: &str) -> Result<Condition, ParseError> {
+    let Some(operator_idx) = cond.find(['>', '<']) else {
+        return Err(ParseError::at(
+            input,
+            cond,
+            "expected a condition like 'a<2006'",
+        ));
+    };
+    let (operand, value) = (&cond[..operator_idx], &cond[operator_idx + 1..]);
+    let operator = match &cond[operator_idx..operator_idx + 1] {
+        ">" => Operator::Gt,
+        _ => Operator::Lt,
+    };
+    let operand = match operand {
+        "x" => Operand::X,
+        "m" => Operand::M,
+        "a" => Operand::A,
+        "s" => Operand::S,
+        _ => {
+            return Err(ParseError::at(
+                input,
+                operand,
+                format!("unknown category '{}'", operand),
+            ))
+        }
+    };
+    Ok(Condition {
+        operand,
+        operator,
+        value: parse_number(input, value)?,
+    })
+}
+
+fn parse_workflows(input: &str, list: &str) -> Result<HashMap<String, Workflow>, ParseError> {
+    let mut workflows = HashMap::new();
+    // Targets of the rules, to check that they exist once all workflows are known
+    let mut targets = vec![];
+
+    for line in list.lines() {
+        let mut wf = Workflow::new();
+
+        let rules = line
+            .split_once('{')
+            .and_then(|(name, rules)| Some((name, rules.strip_suffix('}')?)));
+        let Some((name, rules)) = rules else {
+            return Err(ParseError::at(input, line, "expected '<name>{<rules>}'"));
+        };
+
+        for r in rules.split(',') {
+            let mut rule = Rule {
+                condition: None,
+                target: Target::Reject,
+            };
+            let tgt: &str;
+            if let Some((cond, target)) = r.split_once(':') {
+                // This rule has one or more conditions
+                // Parse conditions
+                rule.condition = Some(parse_condition(input, cond)?);
+                // Set target to the right hand side of the ':'
+                tgt = target;
+            } else {
+                // This rule is only a target
+                tgt = r;
+            }
+
+            // Handle target type
+            match tgt {
+                "A" => rule.target = Target::Accept,
+                "R" => rule.target = Target::Reject,
+                "" => return Err(ParseError::at(input, tgt, "missing rule target")),
+                val => {
+                    rule.target = Target::GoTo(val.to_string());
+                    targets.push((name, val));
+                }
+            }
+            wf.rules.push(rule);
+        }
+        if wf.rules.last().unwrap().condition.is_some() {
+            return Err(ParseError::at(
+                input,
+                &rules[rules.len()..],
+                "the last rule of a workflow must not have a condition",
+            ));
+        }
+        workflows.insert(name.to_string(), wf);
+    }
+
+    if !workflows.contains_key("in") {
+        return Err(ParseError::at(input, list, "no workflow named 'in'"));
+    }
+    if let Some((_, target)) = targets.iter().find(|(_, t)| !workflows.contains_key(*t)) {
+        return Err(ParseError::at(
+            input,
+            target,
+            format!("unknown workflow '{}'", target),
+        ));
+    }
+    // The parts would go round a loop forever
+    if let Some((name, target)) = find_loop(&targets) {
+        return Err(ParseError::at(
+            input,
+            target,
+            format!("workflow '{}' sends parts back to '{}'", name, target),
+        ));
+    }
+    Ok(workflows)
+}
+
+/// A workflow and the target of one of its rules which closes a loop of workflows,
+/// given the targets of the rules of each workflow.
+fn find_loop<'a>(targets: &[(&'a str, &'a str)]) -> Option<(&'a str, &'a str)> {
+    let mut next: HashMap<&str, Vec<&str>> = HashMap::new();
+    for &(name, target) in targets {
+        next.entry(name).or_default().push(target);
+    }
+    let mut visited = HashSet::new();
+    let mut on_path = HashSet::new();
+    targets
+        .iter()
+        .find_map(|&(name, _)| visit_targets(name, &next, &mut visited, &mut on_path))
+}
+
+/// Depth first search for `find_loop`, where `on_path` holds the workflows leading to
+/// `name`.
+fn visit_targets<'a>(
+    name: &'a str,
+    next: &HashMap<&'a str, Vec<&'a str>>,
+    visited: &mut HashSet<&'a str>,
+    on_path: &mut HashSet<&'a str>,
+) -> Option<(&'a str, &'a str)> {
+    if !visited.insert(name) {
+        return None;
+    }
+    on_path.insert(name);
+    for &target in next.get(name).into_iter().flatten() {
+        if on_path.contains(target) {
+            return Some((name, target));
+        }
+        if let Some(found) = visit_targets(target, next, visited, on_path) {
+            return Some(found);
+        }
+    }
+    on_path.remove(name);
+    None
+}
+
+#[derive(Default, Debug)]
+struct PartRating {
+    x: u32,
+    m: u32,
+    a: u32,
+    s: u32,
+}
+
+fn parse_parts(input: &str, list: &str) -> Result<Vec<PartRating>, ParseError> {
+    let mut parts = vec![];
+    for line in list.lines() {
+        let mut part = PartRating::default();
+        let Some(ratings) = line.strip_prefix('{').and_then(|l| l.strip_suffix('}')) else {
+            return Err(ParseError::at(
+                input,
+                line,
+                "expected '{x=..,m=..,a=..,s=..}'",
+            ));
+        };
+        for category_rating in ratings.split(',') {
+            let Some((category, value)) = category_rating.split_once('=') else {
+                return Err(ParseError::at(
+                    input,
+                    category_rating,
+                    "expected '<category>=<rating>'",
+                ));
+            };
+            let value = parse_number(input, value)?;
+
+            match category {
+                "x" => part.x = value,
+                "m" => part.m = value,
+                "a" => part.a = value,
+                "s" => part.s = value,
+                _ => {
+                    return Err(ParseError::at(
+                        input,
+                        category,
+                        format!("unknown category '{}'", category),
+                    ))
+                }
+            };
+        }
+        parts.push(part);
+    }
+
+    Ok(parts)
+}
+
+fn apply_condition(part: &PartRating, condition: &Condition) -> bool {
+    use Operand::*;
+    let compare = match condition.operator {
+        Operator::Gt => |a, b| a > b,
+        Operator::Lt => |a, b| a < b,
+    };
+    match condition.operand {
+        X => compare(part.x, condition.value),
+        M => compare(part.m, condition.value),
+        A => compare(part.a, condition.value),
+        S => compare(part.s, condition.value),
+    }
+}
+
+fn apply_workflow(
+    part: &PartRating,
+    workflow_name: &str,
+    workflows: &HashMap<String, Workflow>,
+) -> bool {
+    let workflow = &workflows[workflow_name];
+    for rule in &workflow.rules {
+        // If we pass the rule condition, we return the result of the target workflow
+        // If we fail the rule condition, we go to the next rule
+        // If there is no condition, we follow the target
+        if rule.condition.is_none() {
+            match &rule.target {
+                Target::Accept => return true,
+                Target::Reject => return false,
+                Target::GoTo(next) => return apply_workflow(part, next.as_str(), workflows),
+            }
+        } else if let Some(cond) = &rule.condition {
+            // Check if part passes condition
+            let passed = apply_condition(part, cond);
+            if passed {
+                // Follow target
+                match &rule.target {
+                    Target::Accept => return true,
+                    Target::Reject => return false,
+                    Target::GoTo(next) => return apply_workflow(part, next.as_str(), workflows),
+                }
+            } else {
+                // Go to next rule
+            }
+        }
+    }
+    unreachable!();
+}
+
+/// The workflows a part goes through from "in", followed by "A" or "R".
+fn route<'a>(part: &PartRating, workflows: &'a HashMap<String, Workflow>) -> Vec<&'a str> {
+    let mut route = vec!["in"];
+    loop {
+        let workflow = &workflows[*route.last().unwrap()];
+        // The last rule has no condition, so some rule always matches
+        let rule = workflow
+            .rules
+            .iter()
+            .find(|rule| match &rule.condition {
+                Some(cond) => apply_condition(part, cond),
+                None => true,
+            })
+            .unwrap();
+        match &rule.target {
+            Target::Accept => return [route, vec!["A"]].concat(),
+            Target::Reject => return [route, vec!["R"]].concat(),
+            Target::GoTo(next) => route.push(next),
+        }
+    }
+}
+
+/// Determine how many combinations of part ratings are accepted by the given workflow.
+/// Uses recursion when the workflow sends some parts through a different workflow in a conditional branch.
+fn count_combinations_accepted_by_workflow(
+    parts: &Parts,
+    workflow_name: &str,
+    workflows: &HashMap<String, Workflow>,
+) -> u64 {
+    // parts is the subspace of the initial input space which reaches this workflow
+
+    let workflow = &workflows[workflow_name];
+
+    // Combinations not matched by the previous rules
+    let mut rest = *parts;
+    // Keeps track of the number of accepted parts from previous loop iterations
+    let mut hold = 0;
+
+    for rule in &workflow.rules {
+        if let Some(cond) = &rule.condition {
+            let axis = cond.operand as usize;
+            trace!("{:?}", cond);
+            // Add to the hold the number of combinations accepted by this rule
+            hold += count_accepted_by_target(
+                &rule.target,
+                &rest.restrict(axis, cond.passing()),
+                workflows,
+            );
+
+            // The others move to the next rule
+            rest = rest.restrict(axis, cond.failing());
+        } else {
+            // No condition -> all combinations are treated the same -> return the total count in this branch
+            return hold + count_accepted_by_target(&rule.target, &rest, workflows);
+        }
+    }
+
+    unreachable!()
+}
+
+// Determine how many combinations of the given parts are accepted by this target
+fn count_accepted_by_target(
+    target: &Target,
+    parts: &Parts,
+    workflows: &HashMap<String, Workflow>,
+) -> u64 {
+    if parts.is_empty() {
+        return 0;
+    }
+    match target {
+        // All parts are acceptable -> tally up the possibilities
+        Target::Accept => parts.volume(),
+        Target::Reject => 0,
+        Target::GoTo(next) => {
+            // Recursively find the number of parts accepted by the target
+            count_combinations_accepted_by_workflow(parts, next, workflows)
+        }
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    let mut blocks = input.split("\n\n");
+    let workflows = parse_workflows(input, blocks.next().unwrap_or_default())?;
+
+    let parts = parse_parts(input, blocks.next().unwrap_or_default())?;
+
+    let score = parts
+        .iter()
+        .filter(|part| apply_workflow(part, "in", &workflows))
+        .map(|part| {
+            [part.x, part.m, part.a, part.s]
+                .map(u64::from)
+                .iter()
+                .sum::<u64>()
+        })
+        .sum::<u64>();
+
+    Ok(score.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    let workflows = parse_workflows(input, input.split("\n\n").next().unwrap_or_default())?;
+
+    let all = Parts::new([RATINGS; 4]);
+    Ok(count_combinations_accepted_by_workflow(&all, "in", &workflows).into())
+}
+
+/// Commands to follow parts through the workflows.
+struct WorkflowSession {
+    workflows: HashMap<String, Workflow>,
+    /// The line of each workflow in the input, to show it
+    lines: HashMap<String, String>,
+    parts: Vec<PartRating>,
+}
+
+impl WorkflowSession {
+    fn workflow(&self, name: &str) -> Result<&Workflow, String> {
+        self.workflows
+            .get(name)
+            .ok_or_else(|| format!("unknown workflow '{}'", name))
+    }
+
+    fn show_route(&self, part: &PartRating) -> String {
+        route(part, &self.workflows).join(" -> ")
+    }
+}
+
+impl Session for WorkflowSession {
+    fn commands(&self) -> &'static [Command] {
+        &[
+            Command {
+                name: "workflows",
+                args: "",
+                help: "list the workflows, and how many rules they have",
+            },
+            Command {
+                name: "show",
+                args: "<workflow>",
+                help: "show the rules of a workflow",
+            },
+            Command {
+                name: "route",
+                args: "x=.. m=.. a=.. s=..",
+                help: "follow a part through the workflows",
+            },
+            Command {
+                name: "part",
+                args: "<n>",
+                help: "follow the n-th part of the input through the workflows",
+            },
+            Command {
+                name: "accepted",
+                args: "<workflow>",
+                help: "count the combinations of ratings which a workflow accepts",
+            },
+        ]
+    }
+
+    fn complete(&self, command: &str, index: usize) -> Vec<String> {
+        match (command, index) {
+            ("show" | "accepted", 0) => self.workflows.keys().cloned().collect(),
+            ("part", 0) => (1..=self.parts.len()).map(|n| n.to_string()).collect(),
+            ("route", 0..=3) => ["x=", "m=", "a=", "s="].map(String::from).to_vec(),
+            _ => vec![],
+        }
+    }
+
+    fn run(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match (command, args) {
+            ("workflows", []) => {
+                let mut names: Vec<_> = self.workflows.keys().collect();
+                names.sort();
+                let lines: Vec<_> = names
+                    .into_iter()
+                    .map(|name| format!("{}: {} rules", name, self.workflows[name].rules.len()))
+                    .collect();
+                Ok(lines.join("\n"))
+            }
+            ("show", [name]) => {
+                self.workflow(name)?;
+                Ok(self.lines[*name].clone())
+            }
+            ("route", [_, ..]) => {
+                // The ratings as in the input, with or without braces and spaces
+                let ratings: Vec<_> = args
+                    .iter()
+                    .flat_map(|arg| arg.trim_matches(['{', '}']).split(','))
+                    .filter(|rating| !rating.is_empty())
+                    .collect();
+                let line = format!("{{{}}}", ratings.join(","));
+                let parts = parse_parts(&line, &line).map_err(|e| e.report(&line))?;
+                Ok(self.show_route(&parts[0]))
+            }
+            ("part", [n]) => {
+                let part = n
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|n| self.parts.get(n.checked_sub(1)?))
+                    .ok_or_else(|| format!("expected a part from 1 to {}", self.parts.len()))?;
+                Ok(format!("{:?}: {}", part, self.show_route(part)))
+            }
+            ("accepted", [name]) => {
+                self.workflow(name)?;
+                let all = Parts::new([RATINGS; 4]);
+                let count = count_combinations_accepted_by_workflow(&all, name, &self.workflows);
+                Ok(count.to_string())
+            }
+            _ => Err(wrong_arguments(self, command)),
+        }
+    }
+}
+
+/// Explore the workflows: which ones a part goes through, and how many parts they accept.
+pub fn explore(input: &str) -> Result<Box<dyn Session>, ParseError> {
+    let mut blocks = input.split("\n\n");
+    let list = blocks.next().unwrap_or_default();
+    let workflows = parse_workflows(input, list)?;
+    let parts = parse_parts(input, blocks.next().unwrap_or_default())?;
+    let lines = list
+        .lines()
+        .filter_map(|line| Some((line.split_once('{')?.0.to_string(), line.to_string())))
+        .collect();
+    Ok(Box::new(WorkflowSession {
+        workflows,
+        lines,
+        parts,
+    }))
+}
+
+/// Random system of `size` workflows and half as many parts. The workflows form a tree
+/// below "in", where each workflow is often sent to by the one created just before it,
+/// which makes long chains of workflows.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut names = vec!["in".to_string()];
+    let mut used = HashSet::from(["in".to_string()]);
+    while names.len() < size {
+        // Longer names when there are too many workflows for 2 or 3 letters
+        let name: String = (0..rng.range(2..=3 + size / 10_000))
+            .map(|_| char::from(rng.range(b'a'..=b'z')))
+            .collect();
+        if used.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+
+    // Each workflow has a few conditional rules and a last rule without condition,
+    // and is the target of a rule of an earlier workflow
+    let mut targets: Vec<Vec<Option<usize>>> = vec![];
+    for workflow in 0..names.len() {
+        if workflow > 0 {
+            // There are always more rules than workflows to send to
+            let (parent, rule) = loop {
+                let parent = if rng.chance(0.5) {
+                    workflow - 1
+                } else {
+                    rng.index(workflow)
+                };
+                let rule = rng.index(targets[parent].len());
+                if targets[parent][rule].is_none() {
+                    break (parent, rule);
+                }
+            };
+            targets[parent][rule] = Some(workflow);
+        }
+        targets.push(vec![None; rng.range(2..=4)]);
+    }
+
+    let mut workflows = vec![];
+    for (name, targets) in names.iter().zip(targets) {
+        let last = targets.len() - 1;
+        let rules: Vec<String> = targets
+            .into_iter()
+            .enumerate()
+            .map(|(rule, target)| {
+                let target = match target {
+                    Some(workflow) => names[workflow].as_str(),
+                    None => rng.choose(&["A", "R"]),
+                };
+                if rule == last {
+                    return target.to_string();
+                }
+                let category = rng.choose(&['x', 'm', 'a', 's']);
+                let operator = rng.choose(&['<', '>']);
+                format!("{}{}{}:{}", category, operator, rng.range(2..=3999), target)
+            })
+            .collect();
+        workflows.push(format!("{}{{{}}}\n", name, rules.join(",")));
+    }
+    rng.shuffle(&mut workflows);
+
+    let mut input = workflows.concat();
+    input.push('\n');
+    for _ in 0..size.div_ceil(2) {
+        let [x, m, a, s] = [(); 4].map(|_| rng.range(1..=4000));
+        input += &format!("{{x={},m={},a={},s={}}}\n", x, m, a, s);
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::ParseError;
+
+    use crate::{explore, part1, part2};
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(19114.into()));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(
+            part2(include_str!("../example.txt")),
+            Ok(167409079868000_u64.into())
+        );
+    }
+
+    #[test]
+    fn test_workflow_loop() {
+        let input = "in{x<10:a,R}\na{m>5:A,in}\n\n{x=1,m=2,a=3,s=4}\n";
+        let error = ParseError::new(2, 9, "workflow 'a' sends parts back to 'in'");
+        assert_eq!(part1(input), Err(error.clone()));
+        assert_eq!(part2(input), Err(error));
+        assert_eq!(
+            part1("in{in}\n\n{x=1,m=2,a=3,s=4}\n"),
+            Err(ParseError::new(
+                1,
+                4,
+                "workflow 'in' sends parts back to 'in'"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_explore_example() {
+        let mut session = explore(include_str!("../example.txt")).unwrap();
+        assert_eq!(
+            session.run("route", &["x=787,m=2655,a=1222,s=2876"]),
+            Ok("in -> qqz -> qs -> lnx -> A".into())
+        );
+        assert_eq!(
+            session.run("route", &["{x=2036,", "m=264,", "a=79,", "s=2244}"]),
+            Ok("in -> qqz -> hdj -> pv -> A".into())
+        );
+        assert_eq!(
+            session.run("part", &["2"]),
+            Ok("PartRating { x: 1679, m: 44, a: 2067, s: 496 }: in -> px -> rfg -> gd -> R".into())
+        );
+        assert_eq!(session.run("show", &["pv"]), Ok("pv{a>1716:R,A}".into()));
+        assert_eq!(
+            session.run("accepted", &["in"]),
+            Ok("167409079868000".into())
+        );
+        assert!(session.run("route", &["x=1,q=2"]).is_err());
+        assert!(session.run("accepted", &["zz"]).is_err());
+        assert!(session.complete("show", 0).contains(&"qkq".to_string()));
+    }
+}
@@ -0,0 +1,90 @@
+use std::{cmp::max, collections::HashMap};
+
+use aoc_common::random::Rng;
+use aoc_common::{parse_number, Answer, ParseError};
+use regex::Regex;
+
+/// Returns the game ID and the maximum number of cubes of each color drawn in it.
+fn max_cubes_in_game(game: &str) -> Result<(u64, HashMap<&str, u64>), ParseError> {
+    // Find game ID
+    let re = Regex::new(r"^Game (\d+):").unwrap();
+    let Some(cap) = re.captures(game) else {
+        return Err(ParseError::at(game, game, "expected 'Game <id>:'"));
+    };
+    let (_full, [id]) = cap.extract();
+
+    let mut max_in_game = HashMap::from([("red", 0), ("green", 0), ("blue", 0)]);
+
+    let re = Regex::new(r"(\d+) (red|green|blue)").unwrap();
+    for draw in game.split(';') {
+        for cap in re.captures_iter(draw) {
+            let (_full, [number, color]) = cap.extract();
+            let tmp_max = max_in_game[color];
+            max_in_game.insert(color, max(tmp_max, parse_number(game, number)?));
+        }
+    }
+
+    Ok((parse_number(game, id)?, max_in_game))
+}
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    let max_values = HashMap::from([("red", 12), ("green", 13), ("blue", 14)]);
+    let mut sum_possible = 0;
+
+    for (line_idx, game) in input.lines().enumerate() {
+        let (id, max_in_game) = max_cubes_in_game(game).map_err(|e| e.below(line_idx))?;
+        let possible = max_in_game
+            .iter()
+            .all(|(color, number)| *number <= max_values[color]);
+
+        if possible {
+            sum_possible += id;
+        }
+    }
+    Ok(sum_possible.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    let mut sum_power = 0;
+
+    for (line_idx, game) in input.lines().enumerate() {
+        let (_, max_in_game) = max_cubes_in_game(game).map_err(|e| e.below(line_idx))?;
+        sum_power += max_in_game["red"] * max_in_game["green"] * max_in_game["blue"];
+    }
+    Ok(sum_power.into())
+}
+
+/// Random record of `size` games, each drawing some of the colors a few times.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for id in 1..=size {
+        let draws: Vec<String> = (0..rng.range(1..=6))
+            .map(|_| {
+                let mut colors = ["red", "green", "blue"];
+                rng.shuffle(&mut colors);
+                colors[..rng.range(1..=3)]
+                    .iter()
+                    .map(|color| format!("{} {}", rng.range(1..=20), color))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect();
+        input += &format!("Game {}: {}\n", id, draws.join("; "));
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(8.into()));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(part2(include_str!("../example.txt")), Ok(2286.into()));
+    }
+}
@@ -0,0 +1,618 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::DerefMut;
+
+use aoc_common::cycle::find_cycle;
+use aoc_common::math::lcm_all;
+use aoc_common::random::Rng;
+use aoc_common::repl::{wrong_arguments, Command, Session};
+use aoc_common::{debug, trace, Answer, ParseError};
+
+#[derive(Debug)]
+enum Module {
+    Broadcaster {
+        outputs: Vec<String>,
+    },
+    Conjunction {
+        outputs: Vec<String>,
+        // Map from source module name to the last pulse type received from it
+        inputs: HashMap<String, PulseType>,
+    },
+    FlipFlop {
+        outputs: Vec<String>,
+        state: PulseType,
+    },
+}
+
+struct ModuleConfig(HashMap<String, RefCell<Module>>);
+
+/// A conjunction sends a low pulse once the last pulses from all its inputs were high
+fn conjunction_pulse(inputs: &HashMap<String, PulseType>) -> PulseType {
+    if inputs.values().all(|pulse| *pulse == PulseType::High) {
+        PulseType::Low
+    } else {
+        PulseType::High
+    }
+}
+
+impl Module {
+    /// The pulse the module sends on
+    fn state(&self) -> PulseType {
+        match self {
+            Module::Conjunction { inputs, .. } => conjunction_pulse(inputs),
+            Module::Broadcaster { .. } => PulseType::Low,
+            Module::FlipFlop { state, .. } => state.clone(),
+        }
+    }
+}
+
+impl ModuleConfig {
+    /// The state of the module `name`, if there is one
+    fn get_state(&self, name: &str) -> Option<PulseType> {
+        self.0.get(name).map(|module| module.borrow().state())
+    }
+
+    fn execute(&mut self, pulse: Pulse, pulse_queue: &mut VecDeque<Pulse>) {
+        pulse.targets.iter().for_each(|name| {
+            trace!("{} -{:?}-> {}", pulse.from, pulse.signal, name);
+            let Some(target) = self.0.get(name) else {
+                return;
+            };
+            match target.borrow_mut().deref_mut() {
+                Module::Broadcaster { outputs } => {
+                    let pulse = Pulse {
+                        from: name.clone(),
+                        signal: pulse.signal.clone(),
+                        targets: outputs.clone(),
+                    };
+                    pulse_queue.push_back(pulse);
+                }
+
+                Module::Conjunction { outputs, inputs } => {
+                    inputs.insert(pulse.from.clone(), pulse.signal.clone());
+                    pulse_queue.push_back(Pulse {
+                        from: name.clone(),
+                        signal: conjunction_pulse(inputs),
+                        targets: outputs.clone(),
+                    });
+                }
+
+                Module::FlipFlop { outputs, state, .. } => {
+                    use PulseType::*;
+                    match pulse.signal {
+                        High => {}
+                        Low => match state {
+                            Low => {
+                                *state = High;
+                                pulse_queue.push_back(Pulse {
+                                    from: name.clone(),
+                                    signal: High,
+                                    targets: outputs.clone(),
+                                });
+                            }
+                            High => {
+                                *state = Low;
+                                pulse_queue.push_back(Pulse {
+                                    from: name.clone(),
+                                    signal: Low,
+                                    targets: outputs.clone(),
+                                });
+                            }
+                        },
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Pulses sent by a single press of the button, past which it is taken to never end
+const MAX_PULSES: usize = 1_000_000;
+
+impl ModuleConfig {
+    /// Push the button once, and return the number of low and high pulses sent, or an
+    /// error if the modules keep sending each other pulses
+    fn press_button(&mut self) -> Result<(usize, usize), ParseError> {
+        // We have to consider pulses in the order that they are sent
+        // Use a FIFO VecDeque -> push_back to add a pulse, pop_front to get the next pulse
+        let mut pulse_queue = VecDeque::from([Pulse {
+            from: "button".into(),
+            signal: PulseType::Low,
+            targets: vec!["broadcaster".into()],
+        }]);
+
+        let mut high_pulse_count = 0;
+        let mut low_pulse_count = 0;
+        while let Some(pulse) = pulse_queue.pop_front() {
+            pulse.targets.iter().for_each(|name| {
+                if name == "output" {
+                    debug!("output: {:?}", pulse);
+                }
+                match pulse.signal {
+                    PulseType::High => high_pulse_count += 1,
+                    PulseType::Low => low_pulse_count += 1,
+                }
+            });
+            if low_pulse_count + high_pulse_count > MAX_PULSES {
+                return Err(ParseError::new(
+                    1,
+                    1,
+                    format!(
+                        "the modules keep sending pulses round a loop, {} pulses after a press",
+                        MAX_PULSES
+                    ),
+                ));
+            }
+            self.execute(pulse, &mut pulse_queue);
+        }
+        Ok((low_pulse_count, high_pulse_count))
+    }
+
+    fn is_conjunction(&self, name: &str) -> bool {
+        self.0
+            .get(name)
+            .is_some_and(|module| matches!(*module.borrow(), Module::Conjunction { .. }))
+    }
+
+    /// Names of the flip-flops whose state can reach the module `name`, sorted
+    fn flip_flops_feeding(&self, name: &str, input: &str) -> Vec<String> {
+        let mut flip_flops = vec![];
+        let mut seen = HashSet::from([name.to_string()]);
+        let mut to_visit = vec![name.to_string()];
+        while let Some(name) = to_visit.pop() {
+            if let Some(module) = self.0.get(&name) {
+                if let Module::FlipFlop { .. } = *module.borrow() {
+                    flip_flops.push(name.clone());
+                }
+            }
+            for src in find_inputs(&name, input) {
+                if seen.insert(src.clone()) {
+                    to_visit.push(src);
+                }
+            }
+        }
+        flip_flops.sort();
+        flip_flops
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum PulseType {
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone)]
+struct Pulse {
+    from: String,
+    signal: PulseType,
+    targets: Vec<String>,
+}
+
+/// Return the name of a module as a String
+fn get_name(module_def: &str) -> String {
+    module_def.split(" -> ").next().unwrap().trim().into()
+}
+
+/// Return the outputs for a given module in vec form
+fn get_outputs(module_def: &str) -> Vec<String> {
+    module_def
+        .split(" -> ")
+        .nth(1)
+        .unwrap()
+        .split(", ")
+        .fold(vec![], |mut acc, x| {
+            acc.push(x.into());
+            acc
+        })
+}
+
+fn find_inputs(name: &str, input: &str) -> Vec<String> {
+    let mut inputs = vec![];
+    for line in input.lines() {
+        let src = get_name(line);
+        let outputs = get_outputs(line);
+        for o in outputs {
+            if o == name {
+                inputs.push(src.trim_start_matches(['%', '&']).into());
+            }
+        }
+    }
+    inputs
+}
+
+fn parse_input(input: &str) -> Result<ModuleConfig, ParseError> {
+    let mut modules: ModuleConfig = ModuleConfig(HashMap::new());
+
+    // Each line is a module
+    for line in input.lines() {
+        let Some((name, _)) = line.split_once(" -> ") else {
+            return Err(ParseError::at(
+                input,
+                line,
+                "expected '<module> -> <outputs>'",
+            ));
+        };
+        if !(name == "broadcaster" || name.starts_with(['%', '&'])) {
+            return Err(ParseError::at(
+                input,
+                name,
+                "expected 'broadcaster', or a module name starting with '%' or '&'",
+            ));
+        }
+        let name = get_name(line);
+        let outputs = get_outputs(line);
+        if name.starts_with("broadcaster") {
+            // broadcaster
+            modules.0.insert(
+                "broadcaster".into(),
+                RefCell::new(Module::Broadcaster { outputs }),
+            );
+        } else if let Some(name) = name.strip_prefix('%') {
+            // flip flop
+            modules.0.insert(
+                name.into(),
+                RefCell::new(Module::FlipFlop {
+                    outputs,
+                    state: PulseType::Low,
+                }),
+            );
+        } else if let Some(name) = name.strip_prefix('&') {
+            // conjunction
+            // Conjunctions start off remembering a low pulse from each input
+            let inputs = find_inputs(name, input)
+                .into_iter()
+                .map(|input| (input, PulseType::Low))
+                .collect();
+            modules.0.insert(
+                name.into(),
+                RefCell::new(Module::Conjunction { inputs, outputs }),
+            );
+        }
+    }
+
+    Ok(modules)
+}
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    let mut modules = parse_input(input)?;
+
+    let mut high_pulse_count = 0;
+    let mut low_pulse_count = 0;
+
+    for _ in 0..1000 {
+        let (low, high) = modules.press_button()?;
+        low_pulse_count += low;
+        high_pulse_count += high;
+    }
+
+    debug!("high: {}, low: {}", high_pulse_count, low_pulse_count);
+    Ok((high_pulse_count as u64 * low_pulse_count as u64).into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    let modules = parse_input(input)?;
+    // rx is fed by a single conjunction, which sends it a low pulse once all its
+    // inputs sent it a high pulse
+    let feeding = match &find_inputs("rx", input)[..] {
+        [name] if modules.is_conjunction(name) => name.clone(),
+        _ => {
+            return Err(ParseError::new(
+                1,
+                1,
+                "expected a single conjunction module sending pulses to rx",
+            ))
+        }
+    };
+    let inputs = find_inputs(&feeding, input);
+    debug!("rx is fed by {}, with inputs {:?}", feeding, inputs);
+
+    // Each input is driven by its own group of flip-flops, counting button presses.
+    // It sends a high pulse when its counter wraps around to its initial state.
+    let mut periods = vec![];
+    for m in &inputs {
+        let modules = parse_input(input)?;
+        let flip_flops = modules.flip_flops_feeding(m, input);
+        // Once a press fails, the state stops changing, which ends the search
+        let mut state = (modules, Ok(()));
+        let cycle = find_cycle(
+            &mut state,
+            |(modules, result)| {
+                if result.is_ok() {
+                    *result = modules.press_button().map(|_| ());
+                }
+            },
+            |(modules, result)| {
+                let states: Vec<_> = flip_flops
+                    .iter()
+                    .map(|name| modules.get_state(name))
+                    .collect();
+                (states, result.is_ok())
+            },
+        );
+        state.1?;
+        debug!(
+            "the {} flip-flops feeding {} repeat every {} presses after the first {}",
+            flip_flops.len(),
+            m,
+            cycle.length,
+            cycle.start
+        );
+        if cycle.start != 0 {
+            return Err(ParseError::new(
+                1,
+                1,
+                format!(
+                    "expected the flip-flops feeding '{}' to return to their initial state",
+                    m
+                ),
+            ));
+        }
+        periods.push((m, cycle.length as u64));
+    }
+
+    // rx gets a low pulse when all the inputs send a high pulse on the same press
+    let steps = lcm_all(periods.iter().map(|&(_, period)| period)).ok_or_else(|| {
+        let periods: Vec<_> = periods
+            .iter()
+            .map(|(m, period)| format!("{} ({})", m, period))
+            .collect();
+        ParseError::new(
+            1,
+            1,
+            format!(
+                "the least common multiple of the periods of {} overflows",
+                periods.join(", ")
+            ),
+        )
+    })?;
+    Ok(steps.into())
+}
+
+/// Commands to press the button, and look at the modules in between.
+struct ModuleSession {
+    input: String,
+    modules: ModuleConfig,
+    presses: usize,
+    /// Low and high pulses sent since the start
+    pulses: (usize, usize),
+}
+
+impl ModuleSession {
+    fn new(input: &str) -> Result<Self, ParseError> {
+        Ok(ModuleSession {
+            input: input.to_string(),
+            modules: parse_input(input)?,
+            presses: 0,
+            pulses: (0, 0),
+        })
+    }
+
+    fn names(&self) -> Vec<&String> {
+        let mut names: Vec<_> = self.modules.0.keys().collect();
+        names.sort();
+        names
+    }
+
+    fn describe(&self, name: &str) -> Result<String, String> {
+        let module = self
+            .modules
+            .0
+            .get(name)
+            .ok_or_else(|| format!("unknown module '{}'", name))?;
+        let (kind, outputs) = match &*module.borrow() {
+            Module::Broadcaster { outputs } => ("broadcaster", outputs.join(", ")),
+            Module::Conjunction { outputs, .. } => ("conjunction", outputs.join(", ")),
+            Module::FlipFlop { outputs, .. } => ("flip-flop", outputs.join(", ")),
+        };
+        let state = match module.borrow().state() {
+            PulseType::Low => "low",
+            PulseType::High => "high",
+        };
+        Ok(format!("{} ({}, {}) -> {}", name, kind, state, outputs))
+    }
+}
+
+impl Session for ModuleSession {
+    fn commands(&self) -> &'static [Command] {
+        &[
+            Command {
+                name: "press",
+                args: "[n]",
+                help: "press the button n times, once by default",
+            },
+            Command {
+                name: "state",
+                args: "[module]",
+                help: "show the state of a module, or of all of them",
+            },
+            Command {
+                name: "reset",
+                args: "",
+                help: "put the modules back in their initial state",
+            },
+        ]
+    }
+
+    fn complete(&self, command: &str, index: usize) -> Vec<String> {
+        match (command, index) {
+            ("state", 0) => self.names().into_iter().cloned().collect(),
+            _ => vec![],
+        }
+    }
+
+    fn run(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match (command, args) {
+            ("press", [] | [_]) => {
+                let n = match args.first() {
+                    Some(n) => n.parse().map_err(|_| format!("invalid number '{}'", n))?,
+                    None => 1,
+                };
+                let (mut low, mut high) = (0, 0);
+                for _ in 0..n {
+                    let pulses = self.modules.press_button().map_err(|e| e.to_string())?;
+                    low += pulses.0;
+                    high += pulses.1;
+                }
+                self.presses += n;
+                self.pulses = (self.pulses.0 + low, self.pulses.1 + high);
+                Ok(format!(
+                    "{} low and {} high pulses, {} low and {} high after {} presses",
+                    low, high, self.pulses.0, self.pulses.1, self.presses
+                ))
+            }
+            ("state", [name]) => self.describe(name),
+            ("state", []) => {
+                let lines: Result<Vec<_>, _> = self
+                    .names()
+                    .iter()
+                    .map(|name| self.describe(name))
+                    .collect();
+                Ok(lines?.join("\n"))
+            }
+            ("reset", []) => {
+                *self = ModuleSession::new(&self.input).map_err(|e| e.to_string())?;
+                Ok(String::new())
+            }
+            _ => Err(wrong_arguments(self, command)),
+        }
+    }
+}
+
+/// Explore the modules: press the button, and see how their states change.
+pub fn explore(input: &str) -> Result<Box<dyn Session>, ParseError> {
+    Ok(Box::new(ModuleSession::new(input)?))
+}
+
+/// Random configuration built like the one of the puzzle, where rx is fed by 4
+/// counters of `size` flip-flops (12 in the puzzle, 63 at most). Each counter counts
+/// the button presses, and resets when it gets to a random odd number with its highest
+/// bit set, sending a high pulse on to rx.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut names = vec![];
+    for a in 'a'..='z' {
+        for b in 'a'..='z' {
+            let name = format!("{}{}", a, b);
+            if name != "rx" {
+                names.push(name);
+            }
+        }
+    }
+    rng.shuffle(&mut names);
+    let bits = size.clamp(1, 63);
+    let last = names.pop().unwrap();
+    let firsts: Vec<_> = (0..4).map(|_| names.pop().unwrap()).collect();
+
+    let mut modules = vec![
+        format!("broadcaster -> {}", firsts.join(", ")),
+        format!("&{} -> rx", last),
+    ];
+    for first in firsts {
+        let (hub, inverter) = (names.pop().unwrap(), names.pop().unwrap());
+        let mut flip_flops = vec![first];
+        flip_flops.extend((1..bits).map(|_| names.pop().unwrap()));
+        let target = (1 << (bits - 1)) | (rng.range(0..=u64::MAX) & ((1 << bits) - 1)) | 1;
+
+        // The flip-flops hold the bits of the count, and pass the carry on to the next
+        // one. The hub sees when the bits of the target are all on, and turns the
+        // others on, to overflow back to zero, and the first one to carry it.
+        let mut hub_outputs = vec![inverter.clone()];
+        for (bit, flip_flop) in flip_flops.iter().enumerate() {
+            let mut outputs = vec![];
+            if let Some(next) = flip_flops.get(bit + 1) {
+                outputs.push(next.clone());
+            }
+            if target >> bit & 1 == 1 {
+                outputs.push(hub.clone());
+            }
+            if bit == 0 || target >> bit & 1 == 0 {
+                hub_outputs.push(flip_flop.clone());
+            }
+            rng.shuffle(&mut outputs);
+            modules.push(format!("%{} -> {}", flip_flop, outputs.join(", ")));
+        }
+        rng.shuffle(&mut hub_outputs);
+        modules.push(format!("&{} -> {}", hub, hub_outputs.join(", ")));
+        modules.push(format!("&{} -> {}", inverter, last));
+    }
+    rng.shuffle(&mut modules);
+    modules
+        .iter()
+        .map(|module| format!("{}\n", module))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{explore, part1, part2};
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(32000000.into()));
+        assert_eq!(part1(include_str!("../example2.txt")), Ok(11687500.into()));
+    }
+
+    #[test]
+    fn test_conjunction_loop() {
+        let input = "broadcaster -> a\n&a -> b\n&b -> a\n";
+        let error = part1(input).unwrap_err();
+        assert!(error.to_string().contains("round a loop"), "{}", error);
+
+        let input = "broadcaster -> a\n&a -> b, c\n&b -> a\n%c -> d\n&d -> rx\n";
+        let error = part2(input).unwrap_err();
+        assert!(error.to_string().contains("round a loop"), "{}", error);
+    }
+
+    #[test]
+    fn test_part2_needs_rx_inputs() {
+        // Part 2 has no example: it relies on rx being fed by a conjunction of counters
+        assert!(part2(include_str!("../example.txt")).is_err());
+        assert!(part2("broadcaster -> a\n%a -> rx\n").is_err());
+    }
+
+    #[test]
+    fn test_part2_counters() {
+        // Two counters of 3 flip-flops, built like the generated ones, reset after 5 and 7
+        // presses
+        let input = "\
+broadcaster -> ab, cd
+%ab -> ef, hh
+%ef -> gg
+%gg -> hh
+&hh -> ab, ef, ii
+&ii -> zz
+%cd -> jj, kk
+%jj -> ll, kk
+%ll -> kk
+&kk -> cd, mm
+&mm -> zz
+&zz -> rx
+";
+        assert_eq!(part2(input), Ok(35.into()));
+    }
+
+    #[test]
+    fn test_explore_example() {
+        let mut session = explore(include_str!("../example2.txt")).unwrap();
+        assert_eq!(
+            session.run("state", &["a"]),
+            Ok("a (flip-flop, low) -> inv, con".into())
+        );
+        assert_eq!(
+            session.run("press", &[]),
+            Ok("4 low and 4 high pulses, 4 low and 4 high after 1 presses".into())
+        );
+        assert_eq!(
+            session.run("state", &["a"]),
+            Ok("a (flip-flop, high) -> inv, con".into())
+        );
+        assert_eq!(
+            session.run("press", &["999"]),
+            Ok("4246 low and 2746 high pulses, 4250 low and 2750 high after 1000 presses".into())
+        );
+        assert_eq!(session.run("reset", &[]), Ok(String::new()));
+        assert_eq!(session.run("state", &[]).unwrap().lines().count(), 5);
+        assert!(session.run("state", &["rx"]).is_err());
+        assert!(session.run("press", &["x"]).is_err());
+    }
+}
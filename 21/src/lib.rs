@@ -0,0 +1,198 @@
+use std::collections::HashSet;
+
+use aoc_common::animation::{Cell, Color, Frame, Screen};
+use aoc_common::geom::{Direction, Point};
+use aoc_common::memo::Memo;
+use aoc_common::random::Rng;
+use aoc_common::{debug, trace, Answer, Grid, ParseError};
+
+type Position = Point<isize>;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Tile {
+    Garden,
+    Rock,
+}
+
+fn parse_input(input: &str) -> Result<(Position, Grid<Tile>), ParseError> {
+    let tiles = Grid::parse(input, |c| "S.#".contains(c).then_some(c))?;
+    if input.matches('S').count() != 1 {
+        return Err(ParseError::new(1, 1, "expected exactly one starting tile 'S'"));
+    }
+
+    let (i, j) = tiles.position(|&c| c == 'S').unwrap();
+    let map = tiles.map(|&c| match c {
+        '.' | 'S' => Tile::Garden,
+        _ => Tile::Rock,
+    });
+    Ok((Point::new(i as isize, j as isize), map))
+}
+
+fn get_reachable_tiles(n_steps: usize, current_pos: HashSet<Position>, map: &Grid<Tile>) -> HashSet<Position> {
+    if n_steps == 0 {
+        return current_pos;
+    }
+
+    let mut reachable = HashSet::new();
+
+
+    for pos in current_pos {
+        for direction in Direction::ALL {
+            let next = pos + direction.vector();
+            if let Some(tile) = map.get_signed(next.x, next.y) {
+                if *tile == Tile::Garden {
+                    reachable.insert(next);
+                }
+            }
+        }
+    }
+
+    get_reachable_tiles(n_steps-1, reachable, map)
+}
+
+/// Number of garden plots reachable in exactly `n_steps` steps
+fn count_reachable(input: &str, n_steps: usize) -> Result<usize, ParseError> {
+    let (start, tiles) = parse_input(input)?;
+    debug!("start at {}", start);
+
+    let r = get_reachable_tiles(n_steps, HashSet::from([start]), &tiles);
+
+    Ok(r.len())
+}
+
+/// Number of steps taken in part 1
+const STEPS: usize = 50;
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    Ok(count_reachable(input, STEPS)?.into())
+}
+
+/// Replay the steps of part 1, showing the plots reachable after each step, and the
+/// frontier of the ones reached for the first time
+pub fn animate(input: &str, screen: &mut dyn Screen) -> Result<(), ParseError> {
+    let (start, tiles) = parse_input(input)?;
+    let mut current = HashSet::from([start]);
+    let mut reached = HashSet::new();
+    for step in 0..=STEPS {
+        if step > 0 {
+            current = get_reachable_tiles(1, current, &tiles);
+        }
+        let frame = Frame::from_grid(&tiles, |(x, y), tile| {
+            let position = Point::new(x as isize, y as isize);
+            if current.contains(&position) {
+                // The plots reached for the first time are the frontier
+                if reached.contains(&position) {
+                    Cell::new('O', Color::Green)
+                } else {
+                    Cell::new('O', Color::Yellow)
+                }
+            } else if *tile == Tile::Rock {
+                Cell::new('#', Color::Gray)
+            } else if position == start {
+                Cell::new('S', Color::Red)
+            } else {
+                Cell::new('.', Color::Default)
+            }
+        });
+        let caption = format!("step {}: {} garden plots reachable", step, current.len());
+        if !screen.show(&frame.with_caption(caption)) {
+            break;
+        }
+        reached.extend(current.iter().copied());
+    }
+    Ok(())
+}
+
+/// Garden plots next to each plot of the map, which are the same on every copy of the map
+type NeighbourMemo = Memo<Position, Vec<Position>>;
+
+fn get_reachable_tiles_p2(n_steps: usize, current_pos: HashSet<Position>, map: &Grid<Tile>, memo: &mut NeighbourMemo) -> HashSet<Position> {
+    if n_steps == 0 {
+        return current_pos;
+    }
+
+    let mut reachable = HashSet::new();
+    let (width, height) = (map.width() as isize, map.height() as isize);
+
+
+    for pos in current_pos {
+        // The plot on the map, and the copy of the map it is on
+        let rem = Point::new(pos.x.rem_euclid(width), pos.y.rem_euclid(height));
+        let offset = pos - rem;
+        trace!("{} -> {} {:?}", pos, rem, offset);
+
+        let neighbours = memo.get_or_insert_with(rem, |&rem| {
+            Direction::ALL
+                .iter()
+                .map(|direction| rem + direction.vector())
+                .filter(|next| *map.get_wrapped(next.x, next.y) == Tile::Garden)
+                .collect()
+        });
+        reachable.extend(neighbours.iter().map(|&next| next + offset));
+    }
+
+    get_reachable_tiles_p2(n_steps-1, reachable, map, memo)
+}
+
+/// Number of garden plots reachable in exactly `n_steps` steps, on the infinitely repeated map
+fn count_reachable_p2(input: &str, n_steps: usize) -> Result<usize, ParseError> {
+    let (start, tiles) = parse_input(input)?;
+    debug!("start at {}, map of {}x{}", start, tiles.width(), tiles.height());
+
+    let mut memo = Memo::new();
+    let r = get_reachable_tiles_p2(n_steps, HashSet::from([start]), &tiles, &mut memo);
+    debug!("memo: {}", memo.stats());
+    memo.stats().record();
+
+    Ok(r.len())
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    Ok(count_reachable_p2(input, 50)?.into())
+}
+
+/// Random map of `size` by `size` tiles, one more if `size` is even, with the start in
+/// the middle. Like in the puzzle, the row and the column of the start are clear of
+/// rocks, and so are the sides of the map.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size | 1;
+    let middle = size / 2;
+    let map = Grid::from_fn(size, size, |(x, y)| {
+        if (x, y) == (middle, middle) {
+            'S'
+        } else if x == middle || y == middle || x == 0 || y == 0 || x == size - 1 || y == size - 1 {
+            '.'
+        } else if rng.chance(0.15) {
+            '#'
+        } else {
+            '.'
+        }
+    });
+    map.to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{animate, count_reachable, count_reachable_p2};
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(count_reachable(include_str!("../example.txt"), 6), Ok(16));
+    }
+
+    #[test]
+    fn test_animate_example() {
+        let mut frames = vec![];
+        animate(include_str!("../example.txt"), &mut frames).unwrap();
+        assert_eq!(frames.len(), 51);
+        assert_eq!(frames[6].caption, "step 6: 16 garden plots reachable");
+    }
+
+    #[test]
+    fn test_part2_example() {
+        let input = include_str!("../example.txt");
+        assert_eq!(count_reachable_p2(input, 6), Ok(16));
+        assert_eq!(count_reachable_p2(input, 10), Ok(50));
+        assert_eq!(count_reachable_p2(input, 50), Ok(1594));
+    }
+}
@@ -0,0 +1,160 @@
+use aoc_common::random::Rng;
+use aoc_common::{debug, trace, Answer, Grid, ParseError};
+use regex::Regex;
+
+fn is_symbol(c: &char) -> bool {
+    !c.is_ascii_digit() && c != &'.'
+}
+#[derive(Debug, Clone)]
+struct DigitGroup {
+    digits: Vec<u32>,
+    positions: Vec<(usize, usize)>,
+}
+
+impl DigitGroup {
+    fn value(&self) -> u64 {
+        self.digits
+            .iter()
+            .fold(0, |acc_group, &digit| acc_group * 10 + u64::from(digit))
+    }
+}
+
+// Represent as a grid of chars
+fn parse_grid(input: &str) -> Result<Grid<char>, ParseError> {
+    // Any character other than a digit or '.' is a symbol
+    Grid::parse(input, |c| (!c.is_whitespace()).then_some(c))
+}
+
+fn find_part_numbers(input: &str, grid: &Grid<char>) -> Vec<DigitGroup> {
+    // First, find all groups of digits using a regex and mark down their location, value, etc
+    let re_digits = Regex::new(r"\d+").unwrap();
+
+    let mut digit_groups: Vec<DigitGroup> = vec![];
+    input.lines().enumerate().for_each(|(y_idx, line)| {
+        re_digits.find_iter(line).for_each(|m| {
+            trace!(
+                "number {} at line {}, column {}",
+                m.as_str(),
+                y_idx + 1,
+                m.start() + 1
+            );
+            let mut p = DigitGroup {
+                digits: vec![],
+                positions: vec![],
+            };
+            m.as_str().chars().enumerate().for_each(|(idx, c)| {
+                p.digits.push(c.to_digit(10).unwrap());
+                p.positions.push((m.start() + idx, y_idx));
+            });
+            digit_groups.push(p);
+        });
+    });
+
+    debug!("{} numbers", digit_groups.len());
+
+    // Then, filter them based on whether a symbol is adjacent to any of their digits
+    let mut part_numbers: Vec<DigitGroup> = vec![];
+
+    digit_groups.iter().for_each(|group| {
+        // Turn true if any char adjacent to the group is a symbol
+        let is_part = group.positions.iter().any(|&position| {
+            grid.neighbours8(position)
+                .any(|neighbour| is_symbol(&grid[neighbour]))
+        });
+        if is_part {
+            part_numbers.push(group.clone());
+        }
+    });
+    debug!("{} part numbers", part_numbers.len());
+    part_numbers
+}
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    let grid = parse_grid(input)?;
+    let part_numbers = find_part_numbers(input, &grid);
+    let sum_of_part_numbers = part_numbers
+        .iter()
+        .fold(0_u64, |acc_total, x| x.value() + acc_total);
+    Ok(sum_of_part_numbers.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    let grid = parse_grid(input)?;
+    let part_numbers = find_part_numbers(input, &grid);
+
+    // Find all star symbols
+    let star_symbols: Vec<(usize, usize)> = grid
+        .iter()
+        .filter(|(_, c)| **c == '*')
+        .map(|(position, _)| position)
+        .collect();
+    debug!("{} star symbols", star_symbols.len());
+
+    // Find star symbols that have two adjacent part numbers !
+    let gear_ratios: Vec<u64> = star_symbols.iter().fold(vec![], |mut acc, ss| {
+        // Turn true if exactly two part numbers are adjacent to this star symbol
+        let mut gear_ratio = 1;
+        let mut n_adjacent = 0; // adjacent part numbers
+        for dg in &part_numbers {
+            // Turn true if this part number is adjacent to the star symbol
+            let mut is_adjacent = false;
+            for (digit_x, digit_y) in &dg.positions {
+                if digit_x.abs_diff(ss.0) <= 1 && digit_y.abs_diff(ss.1) <= 1 {
+                    is_adjacent = true;
+                }
+            }
+            if is_adjacent {
+                n_adjacent += 1;
+                gear_ratio *= dg.value();
+            }
+        }
+        trace!("star at {:?}: {} adjacent part numbers", ss, n_adjacent);
+        if n_adjacent == 2 {
+            acc.push(gear_ratio);
+        }
+        acc
+    });
+    Ok(gear_ratios.iter().sum::<u64>().into())
+}
+
+/// Random engine schematic of `size` by `size` tiles, with numbers of up to three
+/// digits and scattered symbols, some of which are next to no number.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut grid = Grid::new(size, size, '.');
+    for y in 0..size {
+        let mut x = 0;
+        while x < size {
+            if rng.chance(0.15) {
+                let digits = rng.range(1..=3).min(size - x);
+                grid[(x, y)] = char::from(rng.range(b'1'..=b'9'));
+                for x in x + 1..x + digits {
+                    grid[(x, y)] = char::from(rng.range(b'0'..=b'9'));
+                }
+                x += digits;
+            }
+            // Leave a gap, so that numbers do not run into each other
+            x += 1;
+        }
+    }
+    for position in grid.positions() {
+        if grid[position] == '.' && rng.chance(0.05) {
+            grid[position] = *rng.choose(&['*', '*', '#', '+', '$', '/', '@', '=', '%', '&', '-']);
+        }
+    }
+    grid.to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(4361.into()));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(part2(include_str!("../example.txt")), Ok(467835.into()));
+    }
+}
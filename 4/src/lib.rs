@@ -0,0 +1,202 @@
+use aoc_common::memo::Memo;
+use aoc_common::random::Rng;
+use aoc_common::{debug, parse_number, trace, Answer, ParseError};
+
+struct Card {
+    id: usize,
+    winning_numbers: Vec<i32>,
+    my_numbers: Vec<i32>,
+}
+
+fn parse_numbers(line: &str, numbers: &str) -> Result<Vec<i32>, ParseError> {
+    numbers
+        .split_ascii_whitespace()
+        .map(|elem| parse_number(line, elem))
+        .collect()
+}
+
+// Parse a line like "Card 1: 41 48 83 | 83 86  6 31"
+fn parse_card(line: &str) -> Result<Card, ParseError> {
+    let (Some((card, numbers)), Some(bar)) = (line.split_once(':'), line.find('|')) else {
+        return Err(ParseError::at(
+            line,
+            line,
+            "expected 'Card <id>: <numbers> | <numbers>'",
+        ));
+    };
+    let id = match card.split_ascii_whitespace().collect::<Vec<_>>()[..] {
+        ["Card", id] => parse_number(line, id)?,
+        _ => return Err(ParseError::at(line, card, "expected 'Card <id>'")),
+    };
+    let (winning, mine) = numbers.split_at(bar - card.len() - 1);
+
+    Ok(Card {
+        id,
+        winning_numbers: parse_numbers(line, winning)?,
+        my_numbers: parse_numbers(line, &mine[1..])?,
+    })
+}
+
+fn parse_cards(input: &str) -> Result<Vec<Card>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(line_idx, line)| parse_card(line).map_err(|e| e.below(line_idx)))
+        .collect()
+}
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    let mut total_points: u64 = 0;
+
+    for card in parse_cards(input)? {
+        let winrar = card.winning_numbers;
+        let mine = card.my_numbers;
+
+        // Determine how many points were gained
+        // 0 correct numbers -> 0
+        // n correct numbers -> 2^(n-1)
+        let mut n_correct = 0;
+        for val_mine in mine {
+            if winrar.contains(&val_mine) {
+                n_correct += 1;
+            }
+        }
+        let points = if n_correct == 0 {
+            0
+        } else {
+            2_u64.pow(n_correct - 1)
+        };
+        trace!("card {}: {} points", card.id, points);
+        total_points += points;
+    }
+    Ok(total_points.into())
+}
+
+// Determine how many of my numbers match the winning numbers
+fn get_number_of_matches(winning_numbers: &[i32], my_numbers: &[i32]) -> usize {
+    let mut total = 0;
+    for val in my_numbers {
+        if winning_numbers.contains(val) {
+            total += 1;
+        }
+    }
+    total
+}
+
+// Compute how many scratchcards result from this one
+fn get_number_of_cards(
+    cards: &[Card],
+    idx: usize,
+    memo: &mut Memo<usize, usize>,
+) -> Result<usize, ParseError> {
+    let card = &cards[idx];
+    let card_id = card.id;
+
+    if let Some(&total) = memo.get(&idx) {
+        return Ok(total);
+    }
+
+    // We get one copy of the next n_matches cards
+    let n_matches = get_number_of_matches(&card.winning_numbers, &card.my_numbers);
+    if idx + n_matches >= cards.len() {
+        return Err(ParseError::new(
+            idx + 1,
+            1,
+            format!("card {} wins copies past the last card", card_id),
+        ));
+    }
+    // Include this card -> start at 1
+    let mut total = 1;
+    for next in idx + 1..=idx + n_matches {
+        total += get_number_of_cards(cards, next, memo)?;
+    }
+    trace!(
+        "card {} wins {} cards, {} in total",
+        card_id,
+        n_matches,
+        total
+    );
+    Ok(*memo.insert(idx, total))
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    let cards = parse_cards(input)?;
+    let mut n_cards = 0;
+    let mut memo = Memo::new();
+    for idx in 0..cards.len() {
+        // Total number of scratchcards earned with the current card (including this one)
+        n_cards += get_number_of_cards(&cards, idx, &mut memo)?;
+    }
+    debug!("memo: {}", memo.stats());
+    memo.stats().record();
+    Ok(n_cards.into())
+}
+
+/// Random pile of `size` scratchcards, with 10 winning numbers and 25 numbers each.
+/// Few cards have many matches, otherwise the number of copies in part 2 would grow
+/// exponentially, and no card wins copies past the end of the pile.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = size.to_string().len();
+    let mut input = String::new();
+    for id in 1..=size {
+        let mut numbers: Vec<u32> = (1..100).collect();
+        rng.shuffle(&mut numbers);
+        let (winning, others) = numbers.split_at(10);
+        let mut matches = 0;
+        while matches < 10 && id + matches < size && rng.chance(0.45) {
+            matches += 1;
+        }
+        let mut mine = [&winning[..matches], &others[..25 - matches]].concat();
+        rng.shuffle(&mut mine);
+
+        let list = |numbers: &[u32]| {
+            let numbers: Vec<_> = numbers.iter().map(|n| format!("{:>2}", n)).collect();
+            numbers.join(" ")
+        };
+        input += &format!(
+            "Card {:>width$}: {} | {}\n",
+            id,
+            list(winning),
+            list(&mine),
+            width = width
+        );
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{part1, part2};
+    use aoc_common::ParseError;
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(13.into()));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(part2(include_str!("../example.txt")), Ok(30.into()));
+    }
+
+    #[test]
+    fn test_part1_many_matches() {
+        let numbers: Vec<_> = (1..=40).map(|n| n.to_string()).collect();
+        let numbers = numbers.join(" ");
+        let input = format!("Card 1: {} | {}\n", numbers, numbers);
+        assert_eq!(part1(&input), Ok((1_u64 << 39).into()));
+    }
+
+    #[test]
+    fn test_copies_past_last_card() {
+        let input = "Card 1: 1 2 | 1 2\nCard 2: 1 2 | 3\n";
+        assert_eq!(
+            part2(input),
+            Err(ParseError::new(
+                1,
+                1,
+                "card 1 wins copies past the last card"
+            ))
+        );
+    }
+}
@@ -0,0 +1,577 @@
+use std::collections::BTreeMap;
+
+use aoc_common::interval::{Interval, IntervalSet};
+use aoc_common::random::Rng;
+use aoc_common::repl::{wrong_arguments, Command, Session};
+use aoc_common::{debug, parse_number, trace, Answer, ParseError};
+
+// PART 1
+
+#[derive(Debug)]
+struct RangeMapping {
+    dest: u64,
+    src: u64,
+    len: u64,
+    /// How part 2 moves the values of this mapping, or why it cannot
+    shift: Result<Shift, ParseError>,
+}
+
+/// A mapping in the terms of part 2, which moves whole ranges of values around as i64
+#[derive(Debug, Clone, Copy)]
+struct Shift {
+    /// The values moved by the mapping
+    source: Interval<i64>,
+    /// How far the values of the source are moved
+    delta: i64,
+}
+
+impl RangeMapping {
+    fn contains(&self, x: u64) -> bool {
+        x >= self.src && x < self.src + self.len
+    }
+    fn map(&self, x: u64) -> u64 {
+        if self.contains(x) {
+            // x is at least src, and the destination ends below 2^64, so this cannot
+            // overflow
+            (x - self.src) + self.dest
+        } else {
+            x
+        }
+    }
+}
+
+const RANGE_TOO_LARGE: &str = "range too large, it must end below 2^63";
+const MAPPING_TOO_LARGE: &str = "range too large, it must end below 2^64";
+
+/// The values from `start` to `start + len`, as part 2 moves whole ranges of values
+/// around as i64, or `None` if they do not end below 2^63.
+fn to_interval(start: u64, len: u64) -> Option<Interval<i64>> {
+    let end = i64::try_from(start.checked_add(len)?).ok()?;
+    Some(Interval::new(i64::try_from(start).ok()?, end))
+}
+
+/// The range from `start` to `start + len`, where `token` is the start.
+fn range(input: &str, token: &str, start: u64, len: u64) -> Result<Interval<i64>, ParseError> {
+    to_interval(start, len).ok_or_else(|| ParseError::at(input, token, RANGE_TOO_LARGE))
+}
+
+fn fill_mapping(input: &str, line: &str) -> Result<RangeMapping, ParseError> {
+    if let [dest_token, src_token, len] = line.split_ascii_whitespace().collect::<Vec<_>>()[..] {
+        let dest: u64 = parse_number(input, dest_token)?;
+        let src = parse_number(input, src_token)?;
+        let len = parse_number(input, len)?;
+        for (token, start) in [(dest_token, dest), (src_token, src)] {
+            if start.checked_add(len).is_none() {
+                return Err(ParseError::at(input, token, MAPPING_TOO_LARGE));
+            }
+        }
+        let shift = range(input, dest_token, dest, len).and_then(|target| {
+            let source = range(input, src_token, src, len)?;
+            Ok(Shift {
+                source,
+                // Both starts are at least 0, so this cannot overflow
+                delta: target.start - source.start,
+            })
+        });
+        Ok(RangeMapping {
+            dest,
+            src,
+            len,
+            shift,
+        })
+    } else {
+        Err(ParseError::at(
+            input,
+            line,
+            "expected '<destination> <source> <length>'",
+        ))
+    }
+}
+
+/// The seeds, and the layers of maps they go through to get to a location.
+struct Almanac {
+    seeds: Vec<u64>,
+    layers: Vec<Vec<RangeMapping>>,
+    /// What the values of each layer are, like "soil" after the "seed-to-soil" map
+    names: Vec<String>,
+}
+
+impl Almanac {
+    /// The value of a seed after each layer, from the seed itself to its location
+    fn follow(&self, seed: u64) -> Vec<u64> {
+        let mut values = vec![seed];
+        for layer in &self.layers {
+            let value = *values.last().unwrap();
+            let mapping = layer.iter().find(|mapping| mapping.contains(value));
+            values.push(mapping.map_or(value, |mapping| mapping.map(value)));
+        }
+        values
+    }
+}
+
+/// The numbers of the initial seeds block
+fn seed_tokens(input: &str) -> Result<Vec<&str>, ParseError> {
+    let seeds_block = input.split("\n\n").next().unwrap_or_default();
+    let Some(seeds) = seeds_block.strip_prefix("seeds:") else {
+        return Err(ParseError::at(input, seeds_block, "expected 'seeds:'"));
+    };
+    Ok(seeds.split_ascii_whitespace().collect())
+}
+
+fn parse_almanac(input: &str) -> Result<Almanac, ParseError> {
+    let seeds = seed_tokens(input)?
+        .into_iter()
+        .map(|seed| parse_number(input, seed))
+        .collect::<Result<_, _>>()?;
+
+    // map blocks, after the seeds
+    let mut layers = vec![];
+    let mut names = vec![];
+    let blocks = input.split("\n\n").skip(1);
+    for block in blocks.filter(|block| !block.trim().is_empty()) {
+        let mut lines = block.lines();
+        let header = lines.next().unwrap_or_default();
+        let Some((_, name)) = header
+            .strip_suffix(" map:")
+            .and_then(|h| h.split_once("-to-"))
+        else {
+            return Err(ParseError::at(
+                input,
+                header,
+                "expected a '<source>-to-<destination> map:' header",
+            ));
+        };
+        names.push(name.to_string());
+        let layer = lines
+            .filter(|line| !line.is_empty())
+            .map(|line| fill_mapping(input, line))
+            .collect::<Result<_, _>>()?;
+        layers.push(layer);
+    }
+
+    Ok(Almanac {
+        seeds,
+        layers,
+        names,
+    })
+}
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    let almanac = parse_almanac(input)?;
+    let mut dests = vec![];
+    for &seed in &almanac.seeds {
+        let dest = *almanac.follow(seed).last().unwrap();
+        dests.push(dest);
+        trace!("seed {} -> location {}", seed, dest);
+    }
+    match dests.iter().min() {
+        Some(min) => Ok((*min).into()),
+        None => Err(ParseError::new(1, 1, "no seeds")),
+    }
+}
+
+/// In part 2, the seeds come in pairs of (start, length), with the token of the start
+fn seed_pairs(input: &str) -> Result<Vec<(&str, u64, u64)>, ParseError> {
+    let tokens = seed_tokens(input)?;
+    if !tokens.len().is_multiple_of(2) {
+        return Err(ParseError::new(
+            1,
+            1,
+            "expected pairs of seed range start and length",
+        ));
+    }
+    tokens
+        .chunks(2)
+        .map(|pair| {
+            let start = parse_number(input, pair[0])?;
+            let len = parse_number(input, pair[1])?;
+            Ok((pair[0], start, len))
+        })
+        .collect()
+}
+
+fn seed_ranges(input: &str) -> Result<IntervalSet<i64>, ParseError> {
+    seed_pairs(input)?
+        .into_iter()
+        .map(|(token, start, len)| range(input, token, start, len))
+        .collect()
+}
+
+fn lowest_location(locations: &IntervalSet<i64>) -> Result<Answer, ParseError> {
+    match locations.min() {
+        Some(min) => Ok(min.into()),
+        None => Err(ParseError::new(1, 1, "no seeds")),
+    }
+}
+
+/// The mappings of a layer in the terms of part 2, or the first which does not fit.
+fn shifts(layer: &[RangeMapping]) -> Result<Vec<Shift>, ParseError> {
+    layer.iter().map(|mapping| mapping.shift.clone()).collect()
+}
+
+/// Move the whole ranges through a layer with set operations: the values in the
+/// source of a mapping are shifted, and the values in no source stay the same.
+fn map_layer(values: &IntervalSet<i64>, layer: &[Shift]) -> IntervalSet<i64> {
+    let mut mapped = IntervalSet::new();
+    let mut unmapped = values.clone();
+    for mapping in layer {
+        let source = IntervalSet::from(mapping.source);
+        let moved = values.intersection(&source).shift(mapping.delta);
+        trace!(
+            "map {} ({:+}): {:?}",
+            mapping.source,
+            mapping.delta,
+            moved.intervals()
+        );
+        mapped = mapped.union(&moved);
+        unmapped = unmapped.difference(&source);
+    }
+    mapped.union(&unmapped)
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    let Almanac { layers, .. } = parse_almanac(input)?;
+    let mut values = seed_ranges(input)?;
+    debug!("seed ranges: {:?}", values.intervals());
+
+    for layer in &layers {
+        values = map_layer(&values, &shifts(layer)?);
+        debug!("ranges after map: {:?}", values.intervals());
+    }
+    lowest_location(&values)
+}
+
+/// Cut the ranges at the start and end of every mapping of a layer, so that each piece
+/// lies in a single mapping, or in none, and only needs a lookup to be moved.
+pub fn part2_optim(input: &str) -> Result<Answer, ParseError> {
+    let Almanac { layers, .. } = parse_almanac(input)?;
+    let mut values = seed_ranges(input)?;
+    debug!("seed ranges: {:?}", values.intervals());
+
+    for layer in &layers {
+        let mut layer = shifts(layer)?;
+        layer.sort_by_key(|mapping| mapping.source.start);
+        let breakpoints = layer.iter().flat_map(|mapping| {
+            let source = mapping.source;
+            [source.start, source.end]
+        });
+        values = values
+            .split(breakpoints)
+            .into_iter()
+            .map(|piece| {
+                // The last mapping starting before the piece is the only one it can be in
+                let below = layer.partition_point(|mapping| mapping.source.start <= piece.start);
+                match below.checked_sub(1).map(|idx| &layer[idx]) {
+                    Some(mapping) if mapping.source.contains(piece.start) => {
+                        trace!(
+                            "piece {} in map {} ({:+})",
+                            piece,
+                            mapping.source,
+                            mapping.delta
+                        );
+                        piece.shift(mapping.delta)
+                    }
+                    _ => piece,
+                }
+            })
+            .collect();
+        debug!("ranges after layer: {:?}", values.intervals());
+    }
+    lowest_location(&values)
+}
+
+/// A range of values, from `low` to `high` excluded
+#[derive(Debug, Clone)]
+struct SeedRange {
+    low: u64,
+    high: u64,
+}
+
+/// Walk along each range in u64, with the mappings of a layer sorted by source: the
+/// values up to the end of the mapping below them move together, and the values below
+/// no mapping stay the same up to the start of the next one.
+pub fn part2_split(input: &str) -> Result<Answer, ParseError> {
+    let Almanac { layers, .. } = parse_almanac(input)?;
+    let mut in_ranges = seed_pairs(input)?
+        .into_iter()
+        .map(|(token, start, len)| match start.checked_add(len) {
+            Some(high) => Ok(SeedRange { low: start, high }),
+            None => Err(ParseError::at(input, token, MAPPING_TOO_LARGE)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for layer in &layers {
+        // Map from the start of the source of each mapping to the mapping
+        let mymap: BTreeMap<u64, &RangeMapping> =
+            layer.iter().map(|mapping| (mapping.src, mapping)).collect();
+        let mut out_ranges = vec![];
+        for range in &in_ranges {
+            let mut cur_seed = range.low;
+            while cur_seed < range.high {
+                // The nearest mapping with src <= cur_seed
+                let below = mymap.range(..=cur_seed).next_back();
+                match below {
+                    Some((_, mapping)) if mapping.contains(cur_seed) => {
+                        // Jump to the end of the mapping's range directly
+                        let range_end = (mapping.src + mapping.len).min(range.high);
+                        out_ranges.push(SeedRange {
+                            low: mapping.map(cur_seed),
+                            high: (range_end - mapping.src) + mapping.dest,
+                        });
+                        cur_seed = range_end;
+                    }
+                    _ => {
+                        // Map unity until the next mapping, or the end of the seed range
+                        let next = mymap.range(cur_seed + 1..).next();
+                        let range_end = next.map_or(range.high, |(&src, _)| src.min(range.high));
+                        out_ranges.push(SeedRange {
+                            low: cur_seed,
+                            high: range_end,
+                        });
+                        cur_seed = range_end;
+                    }
+                }
+            }
+        }
+        debug!("ranges after layer: {:?}", out_ranges);
+        in_ranges = out_ranges;
+    }
+    match in_ranges.iter().map(|range| range.low).min() {
+        Some(min) => Ok(min.into()),
+        None => Err(ParseError::new(1, 1, "no seeds")),
+    }
+}
+
+/// Commands to follow the seeds through the layers of the almanac.
+struct AlmanacSession(Almanac);
+
+fn number(arg: &str) -> Result<u64, String> {
+    arg.parse().map_err(|_| format!("invalid number '{}'", arg))
+}
+
+fn show_ranges(values: &IntervalSet<i64>) -> String {
+    let ranges: Vec<_> = values
+        .intervals()
+        .iter()
+        .map(|range| range.to_string())
+        .collect();
+    ranges.join(" ")
+}
+
+impl Session for AlmanacSession {
+    fn commands(&self) -> &'static [Command] {
+        &[
+            Command {
+                name: "seeds",
+                args: "",
+                help: "list the seeds, and their locations",
+            },
+            Command {
+                name: "seed",
+                args: "<n>",
+                help: "follow a seed through every layer",
+            },
+            Command {
+                name: "range",
+                args: "<start> <length>",
+                help: "follow a range of seeds through every layer",
+            },
+            Command {
+                name: "layers",
+                args: "",
+                help: "list the layers, and how many mappings they have",
+            },
+        ]
+    }
+
+    fn complete(&self, command: &str, index: usize) -> Vec<String> {
+        let seeds = &self.0.seeds;
+        match (command, index) {
+            ("seed", 0) => seeds.iter().map(|seed| seed.to_string()).collect(),
+            ("range", 0) => seeds
+                .iter()
+                .step_by(2)
+                .map(|seed| seed.to_string())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    fn run(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        let almanac = &self.0;
+        let lines: Vec<String> = match (command, args) {
+            ("seeds", []) => almanac
+                .seeds
+                .iter()
+                .map(|&seed| {
+                    format!(
+                        "seed {} -> location {}",
+                        seed,
+                        almanac.follow(seed).last().unwrap()
+                    )
+                })
+                .collect(),
+            ("seed", [seed]) => {
+                let values = almanac.follow(number(seed)?);
+                let steps = ["seed"]
+                    .into_iter()
+                    .chain(almanac.names.iter().map(String::as_str));
+                let steps: Vec<_> = steps
+                    .zip(values)
+                    .map(|(name, value)| format!("{} {}", name, value))
+                    .collect();
+                vec![steps.join(" -> ")]
+            }
+            ("range", [start, len]) => {
+                let range = to_interval(number(start)?, number(len)?).ok_or(RANGE_TOO_LARGE)?;
+                let mut values = IntervalSet::from(range);
+                let mut lines = vec![format!("seed {}", show_ranges(&values))];
+                for (layer, name) in almanac.layers.iter().zip(&almanac.names) {
+                    let layer = shifts(layer).map_err(|e| e.to_string())?;
+                    values = map_layer(&values, &layer);
+                    lines.push(format!("{} {}", name, show_ranges(&values)));
+                }
+                lines
+            }
+            ("layers", []) => almanac
+                .layers
+                .iter()
+                .zip(&almanac.names)
+                .map(|(layer, name)| format!("{}: {} mappings", name, layer.len()))
+                .collect(),
+            _ => return Err(wrong_arguments(self, command)),
+        };
+        Ok(lines.join("\n"))
+    }
+}
+
+/// Explore the almanac: where the seeds, and ranges of them, end up.
+pub fn explore(input: &str) -> Result<Box<dyn Session>, ParseError> {
+    Ok(Box::new(AlmanacSession(parse_almanac(input)?)))
+}
+
+/// Random almanac with 10 ranges of seeds and `size` mappings in each of its layers.
+/// Like in the puzzle, the values are below 2^32, and each layer cuts them into
+/// ranges, most of which it moves around without overlaps. The mappings of a layer
+/// are listed in a random order.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const MAX: u64 = 1 << 32;
+    const NAMES: [&str; 8] = [
+        "seed",
+        "soil",
+        "fertilizer",
+        "water",
+        "light",
+        "temperature",
+        "humidity",
+        "location",
+    ];
+
+    let seeds: Vec<String> = (0..10)
+        .map(|_| {
+            let start = rng.range(0..=MAX - 2);
+            let len = rng.range(1..=(MAX - start).min(500_000_000));
+            format!("{} {}", start, len)
+        })
+        .collect();
+    let mut input = format!("seeds: {}\n", seeds.join(" "));
+
+    for names in NAMES.windows(2) {
+        input += &format!("\n{}-to-{} map:\n", names[0], names[1]);
+        // Cut the values into `size` ranges, and put them back in another order
+        let mut cuts: Vec<u64> = (1..size).map(|_| rng.range(1..=MAX - 1)).collect();
+        cuts.extend([0, MAX]);
+        cuts.sort_unstable();
+        cuts.dedup();
+        let mut ranges: Vec<(u64, u64)> = cuts.windows(2).map(|c| (c[0], c[1] - c[0])).collect();
+        rng.shuffle(&mut ranges);
+        let mut mappings = vec![];
+        let mut dest = 0;
+        for (src, len) in ranges {
+            // Leave some values where they are
+            if !rng.chance(0.1) {
+                mappings.push(format!("{} {} {}\n", dest, src, len));
+            }
+            dest += len;
+        }
+        rng.shuffle(&mut mappings);
+        input += &mappings.concat();
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::ParseError;
+
+    use crate::{
+        explore, part1, part2, part2_optim, part2_split, MAPPING_TOO_LARGE, RANGE_TOO_LARGE,
+    };
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(35.into()));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(part2(include_str!("../example.txt")), Ok(46.into()));
+    }
+
+    #[test]
+    fn test_large_ranges() {
+        let error = |line, column| Err(ParseError::new(line, column, RANGE_TOO_LARGE));
+        // Part 1 follows single seeds, which may be as large as they like, but part 2
+        // takes them as the range 2^63 - 1 to 2^63
+        let input = "seeds: 9223372036854775807 1\n\nseed-to-soil map:\n0 5 2\n";
+        assert_eq!(part1(input), Ok(1.into()));
+        assert_eq!(part2(input), error(1, 8));
+        assert_eq!(part2_optim(input), error(1, 8));
+
+        // Part 1 maps the values in u64, so only part 2 needs the mappings to end below
+        // 2^63
+        let input = "seeds: 1 2\n\nseed-to-soil map:\n0 9223372036854775800 8\n";
+        assert_eq!(part1(input), Ok(1.into()));
+        assert_eq!(part2(input), error(4, 3));
+        assert_eq!(part2_optim(input), error(4, 3));
+        assert_eq!(part2_split(input), Ok(1.into()));
+        let input = "seeds: 1 2\n\nseed-to-soil map:\n18446744073709551615 0 1\n";
+        let too_large = Err(ParseError::new(4, 1, MAPPING_TOO_LARGE));
+        assert_eq!(part1(input), too_large);
+        assert_eq!(part2(input), too_large);
+    }
+
+    #[test]
+    fn test_part2_optim_example() {
+        assert_eq!(part2_optim(include_str!("../example.txt")), Ok(46.into()));
+    }
+
+    #[test]
+    fn test_part2_split_example() {
+        assert_eq!(part2_split(include_str!("../example.txt")), Ok(46.into()));
+    }
+
+    #[test]
+    fn test_explore_example() {
+        let mut session = explore(include_str!("../example.txt")).unwrap();
+        assert_eq!(
+            session.run("seed", &["79"]),
+            Ok(
+                "seed 79 -> soil 81 -> fertilizer 81 -> water 81 -> light 74 -> temperature 78 -> \
+                humidity 78 -> location 82"
+                    .into()
+            )
+        );
+        let range = session.run("range", &["82", "1"]).unwrap();
+        assert_eq!(range.lines().last(), Some("location [46, 47)"));
+        assert_eq!(session.complete("range", 0), vec!["79", "55"]);
+        assert!(session.run("seed", &["x"]).is_err());
+        assert_eq!(
+            session.run("range", &["9223372036854775807", "1"]),
+            Err(RANGE_TOO_LARGE.to_string())
+        );
+        assert!(session
+            .run("range", &["1", "18446744073709551615"])
+            .is_err());
+    }
+}
@@ -0,0 +1,142 @@
+use aoc_common::random::Rng;
+use aoc_common::{debug, parse_number, Answer, ParseError};
+
+/// Returns what follows `label:` on a line like "Time:      7  15   30"
+fn values_after<'a>(
+    input: &'a str,
+    line: Option<&'a str>,
+    label: &str,
+) -> Result<&'a str, ParseError> {
+    let line = line.unwrap_or(&input[input.len()..]);
+    line.strip_prefix(label)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .ok_or_else(|| ParseError::at(input, line, format!("expected '{}:'", label)))
+}
+
+/// The distance travelled overflows. On a race that long, it does as soon as the press
+/// time gets to about the largest integer divided by the race time, so the brute force
+/// stops early.
+const RACE_TOO_LONG: &str = "race too long";
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    let mut lines = input.lines();
+    let time_tokens: Vec<&str> = values_after(input, lines.next(), "Time")?
+        .split_ascii_whitespace()
+        .collect();
+    let times: Vec<i64> = time_tokens
+        .iter()
+        .map(|x| parse_number(input, x))
+        .collect::<Result<_, _>>()?;
+    let distances: Vec<i64> = values_after(input, lines.next(), "Distance")?
+        .split_ascii_whitespace()
+        .map(|x| parse_number(input, x))
+        .collect::<Result<_, _>>()?;
+
+    let mut winner_product: u64 = 1;
+    for ((time, record_distance), token) in times.iter().zip(distances.iter()).zip(time_tokens) {
+        // We can press the button between 1 and time - 1 seconds
+
+        // Brute force
+        let mut winrars: u64 = 0;
+        for press_time in 1..*time {
+            // Speed is equal to press_time / 1 ms
+            // Distance travelled equals speed * time remaining in race
+            //                        =  speed * (time - press_time)
+            //                        =  press_time * (time - press_time)
+            // = press_time * time - press_time^2
+            let travelled_distance = press_time
+                .checked_mul(time - press_time)
+                .ok_or_else(|| ParseError::at(input, token, RACE_TOO_LONG))?;
+            if &travelled_distance > record_distance {
+                winrars += 1;
+            }
+        }
+        debug!("race of {} ms: {} ways to win", time, winrars);
+        winner_product *= winrars;
+    }
+    Ok(winner_product.into())
+}
+
+/// Parse the values of a line as a single number, ignoring the spaces between digits.
+fn parse_kerned_number(input: &str, values: &str) -> Result<u64, ParseError> {
+    values
+        .replace(' ', "")
+        .parse()
+        .map_err(|_| ParseError::at(input, values.trim(), "invalid number"))
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    let mut lines = input.lines();
+    let time_values = values_after(input, lines.next(), "Time")?;
+    let time = parse_kerned_number(input, time_values)?;
+    let record_distance =
+        parse_kerned_number(input, values_after(input, lines.next(), "Distance")?)?;
+    debug!("race of {} ms, record {} mm", time, record_distance);
+
+    // Brute force
+    let mut winrars: u64 = 0;
+    for press_time in 1..time {
+        // Speed is equal to press_time / 1 ms
+        // Distance travelled equals speed * time remaining in race
+        //                        =  speed * (time - press_time)
+        //                        =  press_time * (time - press_time)
+        // = press_time * time - press_time^2
+        let travelled_distance = press_time
+            .checked_mul(time - press_time)
+            .ok_or_else(|| ParseError::at(input, time_values.trim(), RACE_TOO_LONG))?;
+        if travelled_distance > record_distance {
+            winrars += 1;
+        }
+    }
+
+    Ok(winrars.into())
+}
+
+/// Random sheet of `size` races, each of which can be won. Part 2 reads the digits of
+/// all the races as a single race, whose time and distance grow tenfold with each
+/// digit: beyond 4 or 5 races, they no longer fit in its integers.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut times = "Time:    ".to_string();
+    let mut distances = "Distance:".to_string();
+    for _ in 0..size {
+        let time = rng.range(10..=99);
+        // Beaten by holding the button a bit longer
+        let press = rng.range(1..=time / 2 - 1);
+        times += &format!("  {:>5}", time);
+        distances += &format!("  {:>5}", press * (time - press));
+    }
+    format!("{}\n{}\n", times, distances)
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::ParseError;
+
+    use crate::{part1, part2, RACE_TOO_LONG};
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(288.into()));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(part2(include_str!("../example.txt")), Ok(71503.into()));
+    }
+
+    #[test]
+    fn test_race_without_time() {
+        assert_eq!(part1("Time: 0 7\nDistance: 0 9\n"), Ok(0.into()));
+        assert_eq!(part2("Time: 0\nDistance: 0\n"), Ok(0.into()));
+    }
+
+    #[test]
+    fn test_race_too_long() {
+        let error = |column| Err(ParseError::new(1, column, RACE_TOO_LONG));
+        assert_eq!(
+            part1("Time: 7 4611686018427387903\nDistance: 9 1\n"),
+            error(9)
+        );
+        assert_eq!(part2("Time: 9223372 036854775807\nDistance: 1\n"), error(7));
+    }
+}
@@ -0,0 +1,292 @@
+use std::{cell::RefCell, collections::HashMap, ops::ControlFlow, rc::Rc};
+
+use aoc_common::math::crt;
+use aoc_common::random::Rng;
+use aoc_common::{debug, Answer, ParseError};
+
+#[derive(Debug, Clone, Copy)]
+enum Instruction {
+    Left,
+    Right,
+}
+
+#[derive(Debug)]
+struct Node {
+    left: String,
+    right: String,
+}
+
+fn parse_instructions(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let line = input.lines().next().unwrap_or_default();
+    if line.is_empty() {
+        return Err(ParseError::new(1, 1, "expected a list of L/R instructions"));
+    }
+    line.char_indices()
+        .map(|(idx, c)| match c {
+            'L' => Ok(Instruction::Left),
+            'R' => Ok(Instruction::Right),
+            _ => Err(ParseError::new(
+                1,
+                idx + 1,
+                format!("unknown instruction '{}'", c),
+            )),
+        })
+        .collect()
+}
+
+fn parse_nodes(input: &str) -> Result<HashMap<String, Node>, ParseError> {
+    // Node name and its left and right destinations, as slices of the input
+    let mut parsed = vec![];
+    for line in input.lines().skip(2) {
+        let Some((key, destinations)) = line.split_once(" = ") else {
+            return Err(ParseError::at(
+                input,
+                line,
+                "expected '<node> = (<left>, <right>)'",
+            ));
+        };
+        let destinations = destinations
+            .strip_prefix('(')
+            .and_then(|d| d.strip_suffix(')'))
+            .and_then(|d| d.split_once(", "));
+        let Some((left, right)) = destinations else {
+            return Err(ParseError::at(
+                input,
+                line,
+                "expected '<node> = (<left>, <right>)'",
+            ));
+        };
+        parsed.push((key, left, right));
+    }
+
+    // Every destination must be one of the nodes
+    for &(_, left, right) in &parsed {
+        for destination in [left, right] {
+            if !parsed.iter().any(|(key, _, _)| *key == destination) {
+                return Err(ParseError::at(
+                    input,
+                    destination,
+                    format!("unknown node '{}'", destination),
+                ));
+            }
+        }
+    }
+
+    Ok(parsed
+        .into_iter()
+        .map(|(key, left, right)| {
+            let node = Node {
+                left: left.to_string(),
+                right: right.to_string(),
+            };
+            (key.to_string(), node)
+        })
+        .collect())
+}
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    let instructions = parse_instructions(input)?;
+    let nodes = parse_nodes(input)?;
+
+    let Some(mut cur_node) = nodes.get("AAA") else {
+        return Err(ParseError::new(3, 1, "no starting node 'AAA'"));
+    };
+
+    let steps = instructions
+        .iter()
+        .cycle()
+        .enumerate()
+        .try_for_each(|(step_idx, instruction)| {
+            let next_node_name = match instruction {
+                Instruction::Left => &cur_node.left,
+                Instruction::Right => &cur_node.right,
+            };
+            let next_node = nodes.get(next_node_name);
+            cur_node = next_node.unwrap();
+            if next_node_name == "ZZZ" {
+                return ControlFlow::Break(step_idx + 1);
+            }
+
+            ControlFlow::Continue(())
+        });
+
+    match steps {
+        ControlFlow::Break(steps) => Ok(steps.into()),
+        ControlFlow::Continue(()) => unreachable!(),
+    }
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    let instructions = parse_instructions(input)?;
+    let nodes = parse_nodes(input)?;
+
+    // IDEA: instead of doing a hashmap indexing every time, use references to nodes in the node array
+    // This way we can jump directly to left/right without a map access.
+
+    #[derive(Debug)]
+    struct NodeRefs {
+        name: String,
+        left: Option<Rc<RefCell<NodeRefs>>>,
+        right: Option<Rc<RefCell<NodeRefs>>>,
+    }
+
+    let mut node_refs = vec![];
+    for node in &nodes {
+        node_refs.push(Rc::new(RefCell::new(NodeRefs {
+            name: node.0.clone(),
+            left: None,
+            right: None,
+        })));
+    }
+    for node in &node_refs {
+        let orig = nodes.get(&node.borrow().name).unwrap();
+        let left = node_refs
+            .iter()
+            .find(|n| n.borrow().name == orig.left)
+            .unwrap();
+        let right = node_refs
+            .iter()
+            .find(|n| n.borrow().name == orig.right)
+            .unwrap();
+        node.borrow_mut().left = Some(left.clone());
+        node.borrow_mut().right = Some(right.clone());
+    }
+
+    let starting_nodes: Vec<_> = node_refs
+        .iter()
+        .filter(|x| x.borrow().name.ends_with('A'))
+        .collect();
+
+    // Steps at which each ghost is on a node ending with Z: the first time, and then
+    // every cycle
+    let mut z_steps = vec![];
+
+    // For each starting node, find the first node ending with Z
+    for node in starting_nodes {
+        let mut cur = node.clone();
+        let mut first_z = 0;
+        for instruction in instructions.iter().cycle().enumerate() {
+            // Iterate until we get to a node ending with Z
+
+            let left = cur.clone().borrow().left.clone().unwrap();
+            let right = cur.clone().borrow().right.clone().unwrap();
+
+            if cur.borrow().name.ends_with('Z') {
+                if first_z == 0 {
+                    first_z = instruction.0;
+                } else {
+                    let next_z = instruction.0;
+                    debug!(
+                        "{} reaches {} after {} steps, then every {} steps",
+                        node.borrow().name,
+                        cur.borrow().name,
+                        first_z,
+                        next_z - first_z
+                    );
+
+                    z_steps.push((first_z, next_z - first_z));
+                    break;
+                }
+            }
+
+            match instruction.1 {
+                Instruction::Left => cur = left,
+                Instruction::Right => cur = right,
+            }
+        }
+    }
+
+    let congruences = z_steps
+        .iter()
+        .map(|&(first_z, cycle)| (first_z as i128, cycle as i128));
+    let Some((mut steps, period)) = crt(congruences) else {
+        return Err(ParseError::new(
+            1,
+            1,
+            "the ghosts are never all on nodes ending with Z at the same time",
+        ));
+    };
+    // The congruences also hold before the ghosts reach their first Z node
+    let last_first_z = z_steps
+        .iter()
+        .map(|&(first_z, _)| first_z)
+        .max()
+        .unwrap_or(0);
+    while steps < last_first_z as i128 {
+        steps += period;
+    }
+    Ok(steps.into())
+}
+
+/// Names of three letters ending with one of `last`, in a random order.
+fn random_names(rng: &mut Rng, last: impl Fn(char) -> bool) -> Vec<String> {
+    let mut names = vec![];
+    for a in 'A'..='Z' {
+        for b in 'A'..='Z' {
+            for c in ('A'..='Z').filter(|&c| last(c)) {
+                names.push(format!("{}{}{}", a, b, c));
+            }
+        }
+    }
+    rng.shuffle(&mut names);
+    names
+}
+
+/// Random map of about `size` nodes, for 6 ghosts starting on the nodes ending with A,
+/// from AAA to FFA.
+///
+/// Like in the puzzle, each ghost goes round a loop, whatever the instructions, and
+/// meets a single node ending with Z on it, at the end: ZZZ for the ghost starting on
+/// AAA. Every other node of the loop comes in two copies, and the instructions choose
+/// which one the ghost goes through.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut names = random_names(rng, |c| c != 'A' && c != 'Z');
+    let mut ends = random_names(rng, |c| c == 'Z');
+    ends.retain(|name| name != "ZZZ");
+    ends.insert(0, "ZZZ".to_string());
+    // Each ghost has two nodes per step of its loop
+    let longest = (size / 12).clamp(2, names.len() / 12);
+
+    let mut nodes = vec![];
+    for (ghost, end) in "ABCDEF".chars().zip(ends) {
+        let start = format!("{}{}A", ghost, ghost);
+        let length = rng.range(longest.div_ceil(2)..=longest);
+        let mut next = [end.clone(), end.clone()];
+        for _ in 1..length {
+            let pair = [names.pop().unwrap(), names.pop().unwrap()];
+            for name in &pair {
+                nodes.push((name.clone(), next.clone()));
+            }
+            next = pair;
+            rng.shuffle(&mut next);
+        }
+        nodes.push((start, next.clone()));
+        nodes.push((end, next));
+    }
+    rng.shuffle(&mut nodes);
+
+    let instructions: String = (0..rng.range(2..=300))
+        .map(|_| *rng.choose(&['L', 'R']))
+        .collect();
+    let mut input = format!("{}\n\n", instructions);
+    for (name, [left, right]) in nodes {
+        input += &format!("{} = ({}, {})\n", name, left, right);
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(2.into()));
+        assert_eq!(part1(include_str!("../example2.txt")), Ok(6.into()));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(part2(include_str!("../example3.txt")), Ok(6.into()));
+    }
+}
@@ -0,0 +1,87 @@
+use aoc_common::random::Rng;
+use aoc_common::{parse_number, Answer, ParseError};
+
+fn parse_readings(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    input
+        .lines()
+        .map(|l| {
+            l.split_whitespace()
+                .map(|val| parse_number(input, val))
+                .collect()
+        })
+        .collect()
+}
+
+fn get_diff(reading: &[i64]) -> Vec<i64> {
+    reading.windows(2).map(|v| v[1] - v[0]).collect::<Vec<_>>()
+}
+
+fn extrapolate(reading: &[i64]) -> i64 {
+    if reading.iter().all(|v| *v == 0) {
+        0
+    } else {
+        reading.last().unwrap() + extrapolate(&get_diff(reading))
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    let readings = parse_readings(input)?;
+
+    let next_vals = readings.iter().map(|r| extrapolate(r));
+
+    let sum: i64 = next_vals.sum();
+    Ok(sum.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    let readings = parse_readings(input)?;
+
+    let backward_readings: Vec<Vec<i64>> = readings
+        .iter()
+        .map(|r| {
+            let mut revd = r.clone();
+            revd.reverse();
+            revd
+        }).collect();
+    let next_vals = backward_readings.iter().map(|r| extrapolate(r));
+
+    let sum: i64 = next_vals.sum();
+    Ok(sum.into())
+}
+
+/// Random report of `size` histories of 21 values, each given by a polynomial of degree
+/// up to 8, so that the differences reach all zeroes. Some histories are constant, or
+/// even all zeroes.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        // Start from the constant differences, and add them up back to the history
+        let degree = rng.range(0..=8);
+        let mut values = [rng.range(-9..=9_i64); 21];
+        for _ in 0..degree {
+            let mut sum = rng.range(-20..=20);
+            for value in values.iter_mut() {
+                (*value, sum) = (sum, sum + *value);
+            }
+        }
+        let values: Vec<_> = values.iter().map(|value| value.to_string()).collect();
+        input += &values.join(" ");
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{part1, part2};
+
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!("../example.txt")), Ok(114.into()));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(part2(include_str!("../example.txt")), Ok(2.into()));
+    }
+}
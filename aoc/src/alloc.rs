@@ -0,0 +1,94 @@
+//! Global allocator wrapper counting allocations and the memory in use, so that the
+//! benchmarks can report them.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering::Relaxed};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static IN_USE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, keeping count of what goes through it.
+pub struct CountingAlloc;
+
+fn grow(size: usize) {
+    let in_use = IN_USE.fetch_add(size, Relaxed) + size;
+    PEAK.fetch_max(in_use, Relaxed);
+}
+
+fn shrink(size: usize) {
+    IN_USE.fetch_sub(size, Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            if new_size > layout.size() {
+                grow(new_size - layout.size());
+            } else {
+                shrink(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// State of the counters when a measurement started.
+pub struct Start {
+    allocations: u64,
+    in_use: usize,
+}
+
+/// Allocations made since a measurement started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// Number of allocations, counting reallocations.
+    pub allocations: u64,
+    /// Highest amount of memory in use, in bytes, on top of what was already in use
+    /// when the measurement started.
+    pub peak: usize,
+}
+
+/// Start measuring allocations. Measurements must not overlap.
+pub fn start() -> Start {
+    let in_use = IN_USE.load(Relaxed);
+    PEAK.store(in_use, Relaxed);
+    Start {
+        allocations: ALLOCATIONS.load(Relaxed),
+        in_use,
+    }
+}
+
+impl Start {
+    pub fn stats(&self) -> Stats {
+        Stats {
+            allocations: ALLOCATIONS.load(Relaxed) - self.allocations,
+            peak: PEAK.load(Relaxed).saturating_sub(self.in_use),
+        }
+    }
+}
@@ -0,0 +1,334 @@
+//! Running every day on the puzzle inputs of several accounts at once, to compare the
+//! answers and timings of the solvers across them.
+//!
+//! Each subdirectory of the batch directory is a set of inputs, one for each day:
+//!
+//! ```text
+//! inputs/alice/1.txt … inputs/alice/21.txt
+//! inputs/bob/1.txt … inputs/bob/21.txt
+//! ```
+//!
+//! Every part of every day of every set is a job, run on a pool of threads. A job
+//! which panics fails on its own, without stopping the others.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_common::Answer;
+
+use crate::bench::format_time;
+use crate::days::Day;
+use crate::ledger::{self, Ledger, Status};
+use crate::panics;
+
+/// A directory of inputs, named after the account they come from.
+pub struct Set {
+    pub name: String,
+    pub dir: PathBuf,
+}
+
+/// The subdirectories of `dir`, in order of name.
+pub fn sets(dir: &Path) -> Result<Vec<Set>, String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("could not list {}: {}", dir.display(), e))?;
+    let mut sets: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .map(|path| Set {
+            name: path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into(),
+            dir: path,
+        })
+        .collect();
+    if sets.is_empty() {
+        return Err(format!(
+            "no input sets in {}, expected one subdirectory of <day>.txt files per account",
+            dir.display()
+        ));
+    }
+    sets.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(sets)
+}
+
+/// A part of a day to solve on the input of a set.
+pub struct Job<'a> {
+    pub set: &'a Set,
+    pub day: &'static Day,
+    pub part: u8,
+}
+
+/// The jobs solving `parts` of `days` on every set, for one part of a day after the
+/// other, and for each set in turn.
+pub fn jobs<'a>(sets: &'a [Set], days: &[&'static Day], parts: &[u8]) -> Vec<Job<'a>> {
+    let mut jobs = vec![];
+    for &day in days {
+        for &part in parts {
+            jobs.extend(sets.iter().map(|set| Job { set, day, part }));
+        }
+    }
+    jobs
+}
+
+/// The answer of a job.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solved {
+    pub answer: Answer,
+    pub elapsed: Duration,
+    /// How the answer compares with the ledger of the input, if it has one.
+    pub status: Option<Status>,
+}
+
+/// What came of a job: its answer, nothing if the set has no input for the day, or why
+/// it failed.
+pub type Outcome = Result<Option<Solved>, String>;
+
+/// Solve the part of a job on `<day>.txt` in its set, checking the answer against the
+/// ledger next to it.
+pub fn solve(job: &Job) -> Outcome {
+    let path = job.set.dir.join(format!("{}.txt", job.day.day));
+    if !path.exists() {
+        return Ok(None);
+    }
+    let input = std::fs::read_to_string(&path)
+        .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+    let ledger_path = ledger::path_for(&path);
+    let ledger = if ledger_path.exists() {
+        Some(Ledger::load(&ledger_path)?)
+    } else {
+        None
+    };
+
+    let now = Instant::now();
+    let answer = (job.day.part(job.part))(&input)
+        .map_err(|e| format!("invalid input, {}", e.report(&input)))?;
+    let elapsed = now.elapsed();
+    let status = ledger.map(|ledger| ledger.check(job.part, &answer));
+    Ok(Some(Solved {
+        answer,
+        elapsed,
+        status,
+    }))
+}
+
+/// Apply `f` to every item on `threads` threads, and return the results in the order of
+/// the items, or why `f` panicked on them.
+pub fn map<T, R>(items: &[T], threads: usize, f: impl Fn(&T) -> R + Sync) -> Vec<Result<R, String>>
+where
+    T: Sync,
+    R: Send,
+{
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new((0..items.len()).map(|_| None).collect::<Vec<_>>());
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                let result = panics::catch(|| f(item));
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every item is taken by a thread"))
+        .collect()
+}
+
+/// Errors of the jobs, and answers which differ from their ledger.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub failed: usize,
+    pub differing: usize,
+}
+
+/// The table of the outcomes of `jobs`, in the order `jobs()` gives them, with a row
+/// for each part of a day and a column for each set, followed by the errors of the jobs
+/// which failed and the answers which differ from their ledger. Days without an input
+/// in any set are left out.
+pub fn matrix(sets: &[Set], jobs: &[Job], outcomes: &[Outcome]) -> (String, Summary) {
+    let mut summary = Summary::default();
+    let mut errors = vec![];
+    let mut rows = vec![];
+    let mut header = vec!["Day".to_string(), "Part".to_string()];
+    header.extend(sets.iter().map(|set| set.name.clone()));
+    rows.push(header);
+
+    let mut row: Vec<String> = vec![];
+    for (job, outcome) in jobs.iter().zip(outcomes) {
+        if row.is_empty() {
+            row = vec![job.day.day.to_string(), job.part.to_string()];
+        }
+        row.push(match outcome {
+            Ok(Some(solved)) => {
+                let time = format_time(solved.elapsed);
+                match &solved.status {
+                    Some(status @ Status::Fail(_)) => {
+                        summary.differing += 1;
+                        errors.push(format!(
+                            "{} day {} part {}: {} ({})",
+                            job.set.name, job.day.day, job.part, solved.answer, status
+                        ));
+                        format!("{} ({}, FAIL)", solved.answer, time)
+                    }
+                    _ => format!("{} ({})", solved.answer, time),
+                }
+            }
+            Ok(None) => "-".to_string(),
+            Err(e) => {
+                summary.failed += 1;
+                errors.push(format!(
+                    "{} day {} part {}: {}",
+                    job.set.name, job.day.day, job.part, e
+                ));
+                "error".to_string()
+            }
+        });
+        if row.len() == sets.len() + 2 {
+            if row[2..].iter().any(|cell| cell != "-") {
+                rows.push(row);
+            }
+            row = vec![];
+        }
+    }
+
+    let widths: Vec<_> = (0..sets.len() + 2)
+        .map(|column| rows.iter().map(|row| row[column].chars().count()).max())
+        .map(Option::unwrap_or_default)
+        .collect();
+    let mut table = String::new();
+    for row in &rows {
+        let cells: Vec<_> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+    }
+    if !errors.is_empty() {
+        table.push('\n');
+        for error in errors {
+            table.push_str(&error);
+            table.push('\n');
+        }
+    }
+    (table, summary)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn test_map() {
+        panics::install_hook();
+        let items: Vec<u64> = (0..50).collect();
+        let results = map(&items, 4, |&n| {
+            if n % 10 == 3 {
+                panic!("item {}", n);
+            }
+            n * n
+        });
+        assert_eq!(results.len(), 50);
+        assert_eq!(results[7], Ok(49));
+        assert!(results[13].as_ref().unwrap_err().starts_with("item 13 at "));
+        assert_eq!(results.iter().filter(|result| result.is_err()).count(), 5);
+        assert!(map(&[] as &[u64], 4, |&n| n).is_empty());
+    }
+
+    #[test]
+    fn test_matrix() {
+        let sets: Vec<_> = ["alice", "bob"]
+            .into_iter()
+            .map(|name| Set {
+                name: name.to_string(),
+                dir: PathBuf::from(name),
+            })
+            .collect();
+        let days = [days::get(1).unwrap(), days::get(2).unwrap()];
+        let jobs = jobs(&sets, &days, &[1]);
+        let solved = |answer: i128, status| {
+            Ok(Some(Solved {
+                answer: Answer::Number(answer),
+                elapsed: Duration::from_micros(1500),
+                status,
+            }))
+        };
+        let outcomes = [
+            solved(142, Some(Status::Pass)),
+            solved(77, Some(Status::Fail(Answer::Number(78)))),
+            Ok(None),
+            Err("invalid input".to_string()),
+        ];
+        let (table, summary) = matrix(&sets, &jobs, &outcomes);
+        assert_eq!(
+            table,
+            "\
+Day  Part  alice         bob
+1    1     142 (1.5 ms)  77 (1.5 ms, FAIL)
+2    1     -             error
+
+bob day 1 part 1: 77 (FAIL, expected 78)
+bob day 2 part 1: invalid input
+"
+        );
+        assert_eq!(
+            summary,
+            Summary {
+                failed: 1,
+                differing: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_malformed_inputs() {
+        panics::install_hook();
+        let dir = std::env::temp_dir().join(format!("aoc-batch-{}", std::process::id()));
+        let alice = dir.join("alice");
+        let bob = dir.join("bob");
+        std::fs::create_dir_all(&alice).unwrap();
+        std::fs::create_dir_all(&bob).unwrap();
+        // Conjunctions sending each other pulses for ever, and a module without its arrow
+        std::fs::write(alice.join("20.txt"), "broadcaster -> a\n&a -> b\n&b -> a\n").unwrap();
+        std::fs::write(bob.join("20.txt"), "broadcaster -> a\n%a b\n").unwrap();
+        std::fs::write(
+            bob.join("6.txt"),
+            "Time:      7  15   30\nDistance:  9  40  200\n",
+        )
+        .unwrap();
+
+        let sets = sets(&dir).unwrap();
+        let days = [days::get(6).unwrap(), days::get(20).unwrap()];
+        let jobs = jobs(&sets, &days, &[1]);
+        let outcomes: Vec<_> = map(&jobs, 2, solve)
+            .into_iter()
+            .map(|result| result.unwrap_or_else(|e| Err(format!("panicked, {}", e))))
+            .collect();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let (table, summary) = matrix(&sets, &jobs, &outcomes);
+        assert_eq!(summary.failed, 2, "{}", table);
+        assert_eq!(outcomes[0], Ok(None));
+        assert_eq!(
+            outcomes[1]
+                .as_ref()
+                .map(|solved| solved.as_ref().map(|s| s.answer.clone())),
+            Ok(Some(Answer::Number(288)))
+        );
+        assert!(outcomes[2].as_ref().unwrap_err().contains("round a loop"));
+        assert!(outcomes[3].is_err());
+    }
+}
@@ -0,0 +1,212 @@
+//! Timing of the solvers, with the allocations they make, and comparison against a
+//! baseline saved by a previous run.
+
+use std::time::{Duration, Instant};
+
+use aoc_common::ParseError;
+
+use crate::alloc;
+use crate::days::Solver;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measurement {
+    pub day: u8,
+    pub part: u8,
+    pub implementation: String,
+    /// Median wall time over all runs.
+    pub time: Duration,
+    pub allocations: u64,
+    /// Peak memory allocated while solving, in bytes.
+    pub peak: usize,
+}
+
+impl Measurement {
+    fn same_solver(&self, other: &Measurement) -> bool {
+        self.day == other.day
+            && self.part == other.part
+            && self.implementation == other.implementation
+    }
+}
+
+/// Run `solver` on `input` `runs` times (at least once), and return the median time
+/// together with the allocations of the first run.
+pub fn measure(
+    solver: Solver,
+    input: &str,
+    runs: u32,
+) -> Result<(Duration, alloc::Stats), ParseError> {
+    let mut times = vec![];
+    let mut stats = None;
+    for _ in 0..runs.max(1) {
+        let start = alloc::start();
+        let now = Instant::now();
+        solver(input)?;
+        times.push(now.elapsed());
+        stats.get_or_insert(start.stats());
+    }
+    times.sort();
+    Ok((times[times.len() / 2], stats.unwrap()))
+}
+
+pub fn format_time(time: Duration) -> String {
+    let nanos = time.as_nanos();
+    if nanos < 1_000 {
+        format!("{} ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1} µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.1} ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2} s", nanos as f64 / 1e9)
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+/// Header of the table printed by `row`.
+pub fn header() -> String {
+    format!(
+        "{:>3} {:>4}  {:<14} {:>10} {:>10} {:>11}  {}",
+        "Day", "Part", "Implementation", "Time", "Allocs", "Peak memory", "vs baseline"
+    )
+}
+
+/// One line of the results table. When a baseline is given, the time is compared with
+/// the one recorded there for the same solver, and the returned flag tells whether it
+/// is more than `threshold` percent slower.
+pub fn row(
+    measurement: &Measurement,
+    baseline: Option<&[Measurement]>,
+    threshold: u32,
+) -> (String, bool) {
+    let mut slower = false;
+    let comparison = match baseline {
+        None => String::new(),
+        Some(baseline) => match baseline.iter().find(|b| b.same_solver(measurement)) {
+            None => "new".to_string(),
+            Some(base) => {
+                let base = base.time.as_secs_f64().max(1e-9);
+                let change = (measurement.time.as_secs_f64() / base - 1.0) * 100.0;
+                slower = change > threshold as f64;
+                format!("{:+.1}%{}", change, if slower { " (slower)" } else { "" })
+            }
+        },
+    };
+    let line = format!(
+        "{:>3} {:>4}  {:<14} {:>10} {:>10} {:>11}  {}",
+        measurement.day,
+        measurement.part,
+        measurement.implementation,
+        format_time(measurement.time),
+        measurement.allocations,
+        format_bytes(measurement.peak),
+        comparison
+    );
+    (line.trim_end().to_string(), slower)
+}
+
+/// Save measurements as a baseline, one tab separated line per solver:
+/// day, part, implementation, time in nanoseconds, allocations and peak memory in bytes.
+pub fn format_baseline(measurements: &[Measurement]) -> String {
+    let mut text = "# day\tpart\timplementation\ttime_ns\tallocations\tpeak_bytes\n".to_string();
+    for m in measurements {
+        text += &format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\n",
+            m.day,
+            m.part,
+            m.implementation,
+            m.time.as_nanos(),
+            m.allocations,
+            m.peak
+        );
+    }
+    text
+}
+
+/// Read a baseline written by `format_baseline`.
+pub fn parse_baseline(text: &str) -> Result<Vec<Measurement>, String> {
+    let mut measurements = vec![];
+    for (idx, line) in text.lines().enumerate() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = || format!("invalid baseline line {}: '{}'", idx + 1, line);
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [day, part, implementation, time, allocations, peak] = fields[..] else {
+            return Err(invalid());
+        };
+        measurements.push(Measurement {
+            day: day.parse().map_err(|_| invalid())?,
+            part: part.parse().map_err(|_| invalid())?,
+            implementation: implementation.to_string(),
+            time: Duration::from_nanos(time.parse().map_err(|_| invalid())?),
+            allocations: allocations.parse().map_err(|_| invalid())?,
+            peak: peak.parse().map_err(|_| invalid())?,
+        });
+    }
+    Ok(measurements)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days::DEFAULT_IMPLEMENTATION;
+
+    fn measurement(implementation: &str, micros: u64) -> Measurement {
+        Measurement {
+            day: 16,
+            part: 2,
+            implementation: implementation.to_string(),
+            time: Duration::from_micros(micros),
+            allocations: 42,
+            peak: 3 * 1024,
+        }
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let measurements = vec![
+            measurement(DEFAULT_IMPLEMENTATION, 1500),
+            measurement("single thread", 12),
+        ];
+        let text = format_baseline(&measurements);
+        assert_eq!(parse_baseline(&text), Ok(measurements));
+        assert!(parse_baseline("16\t2\tdefault\tfast\t1\t1").is_err());
+        assert!(parse_baseline("16\t2").is_err());
+    }
+
+    #[test]
+    fn test_row_against_baseline() {
+        let baseline = vec![measurement(DEFAULT_IMPLEMENTATION, 1000)];
+        let (line, slower) = row(
+            &measurement(DEFAULT_IMPLEMENTATION, 1500),
+            Some(&baseline),
+            10,
+        );
+        assert!(slower);
+        assert!(line.ends_with("1.5 ms         42     3.0 KiB  +50.0% (slower)"));
+
+        let (line, slower) = row(
+            &measurement(DEFAULT_IMPLEMENTATION, 1050),
+            Some(&baseline),
+            10,
+        );
+        assert!(!slower);
+        assert!(line.ends_with("+5.0%"));
+
+        let (line, slower) = row(&measurement("single thread", 1050), Some(&baseline), 10);
+        assert!(!slower);
+        assert!(line.ends_with("new"));
+
+        let (line, _) = row(&measurement("single thread", 1050), None, 10);
+        assert!(line.ends_with("3.0 KiB"));
+    }
+}
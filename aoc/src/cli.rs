@@ -0,0 +1,758 @@
+use aoc_common::log::Level;
+
+pub const USAGE: &str = "\
+Usage: aoc <command> [options]
+
+Commands:
+    run <day|all>       Run the solvers for one day, or for every day
+    bench <day|all>     Time the solvers, including alternative implementations, and
+                        report their allocations and peak memory
+    animate <day>       Replay the simulation of a day step by step in the terminal
+    generate <day>      Print a random puzzle input for a day, to stress test its solvers
+    compare <day|all>   Check that the implementations of a part agree on the examples
+                        of the day and on random inputs
+    new <day>           Create the crate of a new day, and add it to the workspace and
+                        to the runner
+    repl <day>          Explore the parsed puzzle input of a day with commands, like
+                        following a seed through the maps of day 5
+    batch <dir>         Run every day on each set of inputs in the subdirectories of
+                        <dir>, in parallel, and print a table of the answers
+    help                Print this message
+
+Options:
+    -p, --part <1|2>    Only run the given part
+    -i, --input <path>  Read the puzzle input from <path>, or from stdin if <path> is '-'.
+                        '{day}' in <path> is replaced by the day number.
+    -l, --log <level>   Print the solvers' trace points up to <level>: off, error, warn,
+                        info, debug or trace [default: warn]
+
+Run options:
+    -f, --format <text|json>
+                        Print the answers as text, or as one JSON object per line:
+                        {\"day\":17,\"part\":1,\"answer\":102,\"elapsed\":0.0009}
+                        (elapsed in seconds), and the counters of the solver, if it
+                        has any, in \"metrics\". Anything else goes to stderr.
+    --accept            Record the answers in the ledger of the input, as the right ones
+    --render <path>     Draw the puzzle state of the day to an image: .svg, .ppm or .png
+                        (.svg only for day 18)
+
+Each answer is compared with the ledger of its input, <input>.answers.toml next to the
+input file (e.g. 17/input.answers.toml), and reported as PASS, FAIL or NEW. The run fails
+if an answer differs from the ledger.
+
+Bench options:
+    -r, --runs <n>          Run each solver <n> times and report the median time [default: 10]
+    --save <path>           Save the results as a baseline in <path>
+    --baseline <path>       Compare the results with the baseline saved in <path>, and fail
+                            if a solver got slower
+    --threshold <percent>   Slowdown tolerated before failing the comparison [default: 20]
+
+Animate options:
+    --fps <n>           Frames per second [default: 20]
+    --no-color          Draw without colors, which are only used on a terminal anyway
+
+While the animation plays: space pauses or resumes it, n shows the next frame while
+paused, + and - double or halve the speed, and q stops it.
+
+Generate options:
+    --size <n>          Size of the input: its number of lines, or of tiles on each side
+                        of a map, depending on the day [default: about the size of the
+                        puzzle input]
+    --seed <n>          Seed of the random numbers, to generate the same input again
+                        [default: a random seed, printed to stderr]
+
+Compare options:
+    --inputs <n>        Number of random inputs, generated from seeds 0 to <n> - 1
+                        [default: 100]
+    --size <n>          Size of the random inputs, as for generate [default: the size
+                        of the puzzle input, up to 20]
+
+Compare reads the examples of each day from <day>/example*.txt.
+
+New creates <day>/ with stubs for both parts, for their example tests and for the input
+generator, and an empty <day>/example.txt. Run it from the root of the workspace.
+
+Repl reads its commands from stdin: type help for the commands of the day. On a
+terminal, tab completes the commands and their arguments, up and down go through the
+previous commands, and Ctrl-D or quit stops.
+
+Batch options:
+    -j, --jobs <n>      Number of threads to run the jobs on [default: the number of
+                        CPUs]
+
+Batch reads the input of each day from <dir>/<set>/<day>.txt, and checks the answers
+against their ledger, <dir>/<set>/<day>.answers.toml, if there is one. The batch fails
+if a solver fails or panics, or if an answer differs from the ledger.
+
+Environment:
+    AOC_INPUT           Input to use when --input is not given, same format as --input
+    AOC_LOG             Log level to use when --log is not given
+
+Without --input or AOC_INPUT, each day reads <day>/input.txt.";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Days {
+    One(u8),
+    All,
+}
+
+/// How `run` prints the answers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run {
+        days: Days,
+        part: Option<u8>,
+        input: Option<String>,
+        format: Format,
+        log: Option<Level>,
+        /// Record the answers in the ledger.
+        accept: bool,
+        /// Image file to draw the puzzle state to.
+        render: Option<String>,
+    },
+    Bench {
+        days: Days,
+        part: Option<u8>,
+        input: Option<String>,
+        log: Option<Level>,
+        options: BenchOptions,
+    },
+    Animate {
+        day: u8,
+        input: Option<String>,
+        log: Option<Level>,
+        fps: u32,
+        color: bool,
+    },
+    Generate {
+        day: u8,
+        size: Option<usize>,
+        seed: Option<u64>,
+    },
+    Compare {
+        days: Days,
+        /// Number of random inputs.
+        inputs: u64,
+        size: Option<usize>,
+    },
+    New {
+        day: u8,
+    },
+    Repl {
+        day: u8,
+        input: Option<String>,
+        log: Option<Level>,
+    },
+    Batch {
+        dir: String,
+        part: Option<u8>,
+        log: Option<Level>,
+        /// Number of threads, by default one per CPU.
+        jobs: Option<usize>,
+    },
+    Help,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BenchOptions {
+    pub runs: u32,
+    pub save: Option<String>,
+    pub baseline: Option<String>,
+    /// Slowdown tolerated against the baseline, in percent.
+    pub threshold: u32,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            runs: 10,
+            save: None,
+            baseline: None,
+            threshold: 20,
+        }
+    }
+}
+
+fn parse_day(arg: &str) -> Result<Days, String> {
+    if arg == "all" {
+        return Ok(Days::All);
+    }
+    match arg.parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(Days::One(day)),
+        _ => Err(format!(
+            "invalid day '{}', expected a number from 1 to 25 or 'all'",
+            arg
+        )),
+    }
+}
+
+fn parse_format(arg: &str) -> Result<Format, String> {
+    match arg {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => Err(format!("invalid format '{}', expected text or json", arg)),
+    }
+}
+
+fn parse_part(arg: &str) -> Result<u8, String> {
+    match arg {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("invalid part '{}', expected 1 or 2", arg)),
+    }
+}
+
+/// When running every day, the input must differ per day.
+pub fn check_input_for_all_days(input: &str) -> Result<(), String> {
+    if input.contains("{day}") {
+        Ok(())
+    } else {
+        Err(format!(
+            "input '{}' must contain '{{day}}' when running all days",
+            input
+        ))
+    }
+}
+
+/// Parse the command line arguments, without the program name.
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter();
+
+    let command = match args.next() {
+        Some(command) => command,
+        None => return Ok(Command::Help),
+    };
+
+    match command.as_str() {
+        "run" | "bench" => {
+            let mut days = None;
+            let mut part = None;
+            let mut input = None;
+            let mut format = Format::default();
+            let mut log = None;
+            let mut accept = false;
+            let mut render = None;
+            let mut options = BenchOptions::default();
+            let bench = command == "bench";
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "-p" | "--part" => {
+                        let value = args.next().ok_or("missing value for --part")?;
+                        part = Some(parse_part(&value)?);
+                    }
+                    "-i" | "--input" => {
+                        let value = args.next().ok_or("missing value for --input")?;
+                        input = Some(value);
+                    }
+                    "-l" | "--log" => {
+                        let value = args.next().ok_or("missing value for --log")?;
+                        log = Some(value.parse()?);
+                    }
+                    "-f" | "--format" if !bench => {
+                        let value = args.next().ok_or("missing value for --format")?;
+                        format = parse_format(&value)?;
+                    }
+                    "--accept" if !bench => accept = true,
+                    "--render" if !bench => {
+                        render = Some(args.next().ok_or("missing value for --render")?);
+                    }
+                    "-r" | "--runs" if bench => {
+                        let value = args.next().ok_or("missing value for --runs")?;
+                        options.runs = match value.parse() {
+                            Ok(runs) if runs > 0 => runs,
+                            _ => return Err(format!("invalid number of runs '{}'", value)),
+                        };
+                    }
+                    "--save" if bench => {
+                        options.save = Some(args.next().ok_or("missing value for --save")?);
+                    }
+                    "--baseline" if bench => {
+                        let value = args.next().ok_or("missing value for --baseline")?;
+                        options.baseline = Some(value);
+                    }
+                    "--threshold" if bench => {
+                        let value = args.next().ok_or("missing value for --threshold")?;
+                        options.threshold = value
+                            .parse()
+                            .map_err(|_| format!("invalid threshold '{}'", value))?;
+                    }
+                    _ if days.is_none() && !arg.starts_with('-') => days = Some(parse_day(&arg)?),
+                    _ => return Err(format!("unexpected argument '{}'", arg)),
+                }
+            }
+            let days = days.ok_or(format!("missing day to {}", command))?;
+            if days == Days::All {
+                if let Some(input) = &input {
+                    check_input_for_all_days(input)?;
+                }
+                if render.is_some() {
+                    return Err("--render needs a single day".into());
+                }
+            }
+            if bench {
+                Ok(Command::Bench {
+                    days,
+                    part,
+                    input,
+                    log,
+                    options,
+                })
+            } else {
+                Ok(Command::Run {
+                    days,
+                    part,
+                    input,
+                    format,
+                    log,
+                    accept,
+                    render,
+                })
+            }
+        }
+        "animate" => {
+            let mut day = None;
+            let mut input = None;
+            let mut log = None;
+            let mut fps = 20;
+            let mut color = true;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "-i" | "--input" => {
+                        let value = args.next().ok_or("missing value for --input")?;
+                        input = Some(value);
+                    }
+                    "-l" | "--log" => {
+                        let value = args.next().ok_or("missing value for --log")?;
+                        log = Some(value.parse()?);
+                    }
+                    "--fps" => {
+                        let value = args.next().ok_or("missing value for --fps")?;
+                        fps = match value.parse() {
+                            Ok(fps) if fps > 0 => fps,
+                            _ => {
+                                return Err(format!(
+                                    "invalid number of frames per second '{}'",
+                                    value
+                                ))
+                            }
+                        };
+                    }
+                    "--no-color" => color = false,
+                    _ if day.is_none() && !arg.starts_with('-') => {
+                        day = match parse_day(&arg)? {
+                            Days::One(day) => Some(day),
+                            Days::All => return Err("animate needs a single day".into()),
+                        }
+                    }
+                    _ => return Err(format!("unexpected argument '{}'", arg)),
+                }
+            }
+            Ok(Command::Animate {
+                day: day.ok_or("missing day to animate")?,
+                input,
+                log,
+                fps,
+                color,
+            })
+        }
+        "generate" => {
+            let mut day = None;
+            let mut size = None;
+            let mut seed = None;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--size" => {
+                        let value = args.next().ok_or("missing value for --size")?;
+                        size = Some(
+                            value
+                                .parse()
+                                .map_err(|_| format!("invalid size '{}'", value))?,
+                        );
+                    }
+                    "--seed" => {
+                        let value = args.next().ok_or("missing value for --seed")?;
+                        seed = Some(
+                            value
+                                .parse()
+                                .map_err(|_| format!("invalid seed '{}'", value))?,
+                        );
+                    }
+                    _ if day.is_none() && !arg.starts_with('-') => {
+                        day = match parse_day(&arg)? {
+                            Days::One(day) => Some(day),
+                            Days::All => return Err("generate needs a single day".into()),
+                        }
+                    }
+                    _ => return Err(format!("unexpected argument '{}'", arg)),
+                }
+            }
+            Ok(Command::Generate {
+                day: day.ok_or("missing day to generate an input for")?,
+                size,
+                seed,
+            })
+        }
+        "compare" => {
+            let mut days = None;
+            let mut inputs = 100;
+            let mut size = None;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--inputs" => {
+                        let value = args.next().ok_or("missing value for --inputs")?;
+                        inputs = value
+                            .parse()
+                            .map_err(|_| format!("invalid number of inputs '{}'", value))?;
+                    }
+                    "--size" => {
+                        let value = args.next().ok_or("missing value for --size")?;
+                        size = Some(
+                            value
+                                .parse()
+                                .map_err(|_| format!("invalid size '{}'", value))?,
+                        );
+                    }
+                    _ if days.is_none() && !arg.starts_with('-') => days = Some(parse_day(&arg)?),
+                    _ => return Err(format!("unexpected argument '{}'", arg)),
+                }
+            }
+            Ok(Command::Compare {
+                days: days.ok_or("missing day to compare, or 'all'")?,
+                inputs,
+                size,
+            })
+        }
+        "new" => {
+            let mut day = None;
+            for arg in args {
+                match parse_day(&arg)? {
+                    Days::One(n) if day.is_none() => day = Some(n),
+                    Days::One(_) => return Err(format!("unexpected argument '{}'", arg)),
+                    Days::All => return Err("new needs a single day".into()),
+                }
+            }
+            Ok(Command::New {
+                day: day.ok_or("missing day to create")?,
+            })
+        }
+        "repl" => {
+            let mut day = None;
+            let mut input = None;
+            let mut log = None;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "-i" | "--input" => {
+                        let value = args.next().ok_or("missing value for --input")?;
+                        input = Some(value);
+                    }
+                    "-l" | "--log" => {
+                        let value = args.next().ok_or("missing value for --log")?;
+                        log = Some(value.parse()?);
+                    }
+                    _ if day.is_none() && !arg.starts_with('-') => {
+                        day = match parse_day(&arg)? {
+                            Days::One(day) => Some(day),
+                            Days::All => return Err("repl needs a single day".into()),
+                        }
+                    }
+                    _ => return Err(format!("unexpected argument '{}'", arg)),
+                }
+            }
+            Ok(Command::Repl {
+                day: day.ok_or("missing day to explore")?,
+                input,
+                log,
+            })
+        }
+        "batch" => {
+            let mut dir = None;
+            let mut part = None;
+            let mut log = None;
+            let mut jobs = None;
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "-p" | "--part" => {
+                        let value = args.next().ok_or("missing value for --part")?;
+                        part = Some(parse_part(&value)?);
+                    }
+                    "-l" | "--log" => {
+                        let value = args.next().ok_or("missing value for --log")?;
+                        log = Some(value.parse()?);
+                    }
+                    "-j" | "--jobs" => {
+                        let value = args.next().ok_or("missing value for --jobs")?;
+                        jobs = match value.parse() {
+                            Ok(jobs) if jobs > 0 => Some(jobs),
+                            _ => return Err(format!("invalid number of jobs '{}'", value)),
+                        };
+                    }
+                    _ if dir.is_none() && !arg.starts_with('-') => dir = Some(arg),
+                    _ => return Err(format!("unexpected argument '{}'", arg)),
+                }
+            }
+            Ok(Command::Batch {
+                dir: dir.ok_or("missing directory of input sets")?,
+                part,
+                log,
+                jobs,
+            })
+        }
+        "help" | "-h" | "--help" => Ok(Command::Help),
+        _ => Err(format!("unknown command '{}'", command)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(
+            parse(args("run 17 --part 2 --input path")),
+            Ok(Command::Run {
+                days: Days::One(17),
+                part: Some(2),
+                input: Some("path".to_string()),
+                format: Format::Text,
+                log: None,
+                accept: false,
+                render: None,
+            })
+        );
+        assert_eq!(
+            parse(args("run all -p 1")),
+            Ok(Command::Run {
+                days: Days::All,
+                part: Some(1),
+                input: None,
+                format: Format::Text,
+                log: None,
+                accept: false,
+                render: None,
+            })
+        );
+        assert_eq!(
+            parse(args("run all -i inputs/{day}.txt")),
+            Ok(Command::Run {
+                days: Days::All,
+                part: None,
+                input: Some("inputs/{day}.txt".to_string()),
+                format: Format::Text,
+                log: None,
+                accept: false,
+                render: None,
+            })
+        );
+        assert_eq!(
+            parse(args("run 8 --format json --log debug --accept")),
+            Ok(Command::Run {
+                days: Days::One(8),
+                part: None,
+                input: None,
+                format: Format::Json,
+                log: Some(Level::Debug),
+                accept: true,
+                render: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_render() {
+        assert_eq!(
+            parse(args("run 10 -p 2 --render loop.png")),
+            Ok(Command::Run {
+                days: Days::One(10),
+                part: Some(2),
+                input: None,
+                format: Format::Text,
+                log: None,
+                accept: false,
+                render: Some("loop.png".to_string()),
+            })
+        );
+        assert!(parse(args("run all --render out.svg")).is_err());
+        assert!(parse(args("run 10 --render")).is_err());
+        assert!(parse(args("bench 10 --render out.svg")).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert_eq!(
+            parse(args("bench all")),
+            Ok(Command::Bench {
+                days: Days::All,
+                part: None,
+                input: None,
+                log: None,
+                options: BenchOptions::default(),
+            })
+        );
+        assert_eq!(
+            parse(args(
+                "bench 16 -p 2 -r 3 --baseline base.tsv --threshold 5 -l off"
+            )),
+            Ok(Command::Bench {
+                days: Days::One(16),
+                part: Some(2),
+                input: None,
+                log: Some(Level::Off),
+                options: BenchOptions {
+                    runs: 3,
+                    save: None,
+                    baseline: Some("base.tsv".to_string()),
+                    threshold: 5,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_animate() {
+        assert_eq!(
+            parse(args("animate 16")),
+            Ok(Command::Animate {
+                day: 16,
+                input: None,
+                log: None,
+                fps: 20,
+                color: true,
+            })
+        );
+        assert_eq!(
+            parse(args("animate 14 --fps 5 --no-color -i example.txt")),
+            Ok(Command::Animate {
+                day: 14,
+                input: Some("example.txt".to_string()),
+                log: None,
+                fps: 5,
+                color: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse(args("run")).is_err());
+        assert!(parse(args("run 26")).is_err());
+        assert!(parse(args("run 3 --part 3")).is_err());
+        assert!(parse(args("run all --input path")).is_err());
+        assert!(parse(args("run all --input -")).is_err());
+        assert!(parse(args("jump 3")).is_err());
+        assert!(parse(args("run 3 --runs 5")).is_err());
+        assert!(parse(args("run 3 --format xml")).is_err());
+        assert!(parse(args("run 3 --log loud")).is_err());
+        assert!(parse(args("bench 3 --format json")).is_err());
+        assert!(parse(args("bench 3 --runs 0")).is_err());
+        assert!(parse(args("bench 3 --threshold fast")).is_err());
+        assert!(parse(args("animate all")).is_err());
+        assert!(parse(args("animate 16 --fps 0")).is_err());
+        assert!(parse(args("animate 16 --part 1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_generate() {
+        assert_eq!(
+            parse(args("generate 17 --size 2000 --seed 42")),
+            Ok(Command::Generate {
+                day: 17,
+                size: Some(2000),
+                seed: Some(42),
+            })
+        );
+        assert_eq!(
+            parse(args("generate 5")),
+            Ok(Command::Generate {
+                day: 5,
+                size: None,
+                seed: None,
+            })
+        );
+        assert!(parse(args("generate all")).is_err());
+        assert!(parse(args("generate 5 --size -1")).is_err());
+        assert!(parse(args("generate --seed 1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_compare() {
+        assert_eq!(
+            parse(args("compare all --inputs 100 --size 50")),
+            Ok(Command::Compare {
+                days: Days::All,
+                inputs: 100,
+                size: Some(50),
+            })
+        );
+        assert_eq!(
+            parse(args("compare 14")),
+            Ok(Command::Compare {
+                days: Days::One(14),
+                inputs: 100,
+                size: None,
+            })
+        );
+        assert!(parse(args("compare")).is_err());
+        assert!(parse(args("compare 5 --inputs many")).is_err());
+    }
+
+    #[test]
+    fn test_parse_new() {
+        assert_eq!(parse(args("new 22")), Ok(Command::New { day: 22 }));
+        assert!(parse(args("new")).is_err());
+        assert!(parse(args("new all")).is_err());
+        assert!(parse(args("new 22 23")).is_err());
+        assert!(parse(args("new 26")).is_err());
+    }
+
+    #[test]
+    fn test_parse_repl() {
+        assert_eq!(
+            parse(args("repl 19 -i 19/example.txt")),
+            Ok(Command::Repl {
+                day: 19,
+                input: Some("19/example.txt".to_string()),
+                log: None,
+            })
+        );
+        assert!(parse(args("repl")).is_err());
+        assert!(parse(args("repl all")).is_err());
+        assert!(parse(args("repl 5 --part 1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_batch() {
+        assert_eq!(
+            parse(args("batch inputs -j 4 -p 2")),
+            Ok(Command::Batch {
+                dir: "inputs".to_string(),
+                part: Some(2),
+                log: None,
+                jobs: Some(4),
+            })
+        );
+        assert_eq!(
+            parse(args("batch inputs/")),
+            Ok(Command::Batch {
+                dir: "inputs/".to_string(),
+                part: None,
+                log: None,
+                jobs: None,
+            })
+        );
+        assert!(parse(args("batch")).is_err());
+        assert!(parse(args("batch inputs --jobs 0")).is_err());
+        assert!(parse(args("batch inputs --input 1.txt")).is_err());
+    }
+}
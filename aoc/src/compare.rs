@@ -0,0 +1,162 @@
+//! Differential testing of the implementations of a part: they all run on the same
+//! inputs, the examples of the day and random ones, and must give the same answers.
+//! This shows that the optimised solvers really are equivalent to the simpler ones.
+
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+
+use aoc_common::random::Rng;
+use aoc_common::{Answer, ParseError};
+
+use crate::days::{self, Day};
+
+/// Largest size of the random inputs by default. Small inputs are quick to solve, and
+/// to read through when the implementations disagree, so many more can be tried.
+pub const DEFAULT_SIZE: usize = 20;
+
+/// An input for the implementations to agree on.
+pub struct Case {
+    /// Where the input comes from, to get it again when they disagree.
+    pub origin: String,
+    pub input: String,
+}
+
+/// The examples of a day: the `example*.txt` files in `dir`, in order of name.
+pub fn examples(dir: &Path) -> Result<Vec<Case>, String> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| format!("could not list the examples in {}: {}", dir.display(), e))?;
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            name.starts_with("example") && name.ends_with(".txt")
+        })
+        .collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let input = std::fs::read_to_string(&path)
+                .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+            Ok(Case {
+                origin: path.display().to_string(),
+                input,
+            })
+        })
+        .collect()
+}
+
+/// Random inputs of `size` for a day, one for each seed. By default, they are as large
+/// as the puzzle input, up to `DEFAULT_SIZE`.
+pub fn generated(day: u8, size: Option<usize>, seeds: Range<u64>) -> Vec<Case> {
+    let Some(generation) = days::generator(day) else {
+        return vec![];
+    };
+    let size = size.unwrap_or(generation.size.min(DEFAULT_SIZE));
+    seeds
+        .map(|seed| Case {
+            origin: format!("aoc generate {} --size {} --seed {}", day, size, seed),
+            input: (generation.generate)(&mut Rng::new(seed), size),
+        })
+        .collect()
+}
+
+/// The parts of a day which have more than one implementation to compare.
+pub fn compared_parts(day: &Day) -> Vec<u8> {
+    [1, 2]
+        .into_iter()
+        .filter(|&part| days::alternatives(day.day, part).next().is_some())
+        .collect()
+}
+
+/// Results of the implementations of a part which do not all agree on an input.
+pub struct Disagreement {
+    pub day: u8,
+    pub part: u8,
+    pub origin: String,
+    pub results: Vec<(&'static str, Result<Answer, ParseError>)>,
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {} part {} disagrees on {}:",
+            self.day, self.part, self.origin
+        )?;
+        for (name, result) in &self.results {
+            match result {
+                Ok(answer) => write!(f, "\n    {}: {}", name, answer)?,
+                Err(e) => write!(f, "\n    {}: invalid input, {}", name, e)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Run every implementation of a part on the input of `case`, and return their
+/// results if any of them differs from the others.
+pub fn compare(day: &Day, part: u8, case: &Case) -> Option<Disagreement> {
+    let results: Vec<_> = days::implementations(day, part)
+        .map(|(name, solver)| (name, solver(&case.input)))
+        .collect();
+    let agree = results.windows(2).all(|pair| pair[0].1 == pair[1].1);
+    (!agree).then(|| Disagreement {
+        day: day.day,
+        part,
+        origin: case.origin.clone(),
+        results,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Directory of a day in the workspace, with its examples.
+    fn day_dir(day: u8) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(day.to_string())
+    }
+
+    #[test]
+    fn test_implementations_agree() {
+        for day in days::DAYS {
+            let parts = compared_parts(day);
+            if parts.is_empty() {
+                continue;
+            }
+            let mut cases = examples(&day_dir(day.day)).unwrap();
+            cases.extend(generated(day.day, None, 0..10));
+            for part in parts {
+                for case in &cases {
+                    if let Some(disagreement) = compare(day, part, case) {
+                        panic!("{}", disagreement);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_disagreement() {
+        let disagreement = Disagreement {
+            day: 5,
+            part: 2,
+            origin: "aoc generate 5 --size 30 --seed 7".into(),
+            results: vec![
+                ("default", Ok(46.into())),
+                ("interval sets", Ok(35.into())),
+                ("other", Err(ParseError::new(2, 1, "expected a number"))),
+            ],
+        };
+        assert_eq!(
+            disagreement.to_string(),
+            "day 5 part 2 disagrees on aoc generate 5 --size 30 --seed 7:\n    \
+             default: 46\n    interval sets: 35\n    other: invalid input, line 2, column 1: \
+             expected a number"
+        );
+    }
+}
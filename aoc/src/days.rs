@@ -0,0 +1,222 @@
+use aoc_common::animation::Screen;
+use aoc_common::export::Picture;
+use aoc_common::random::Rng;
+use aoc_common::repl::Session;
+use aoc_common::{Answer, ParseError};
+
+/// A solver for one part of a puzzle, taking the puzzle input as text.
+pub type Solver = fn(&str) -> Result<Answer, ParseError>;
+
+pub struct Day {
+    pub day: u8,
+    pub part1: Solver,
+    pub part2: Solver,
+}
+
+impl Day {
+    pub fn part(&self, part: u8) -> Solver {
+        match part {
+            1 => self.part1,
+            2 => self.part2,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Registry of all the days that have been solved so far, in order.
+#[rustfmt::skip]
+pub const DAYS: &[Day] = &[
+    Day { day: 1, part1: day_1::part1, part2: day_1::part2 },
+    Day { day: 2, part1: day_2::part1, part2: day_2::part2 },
+    Day { day: 3, part1: day_3::part1, part2: day_3::part2 },
+    Day { day: 4, part1: day_4::part1, part2: day_4::part2 },
+    Day { day: 5, part1: day_5::part1, part2: day_5::part2_optim },
+    Day { day: 6, part1: day_6::part1, part2: day_6::part2 },
+    Day { day: 7, part1: day_7::part1, part2: day_7::part2 },
+    Day { day: 8, part1: day_8::part1, part2: day_8::part2 },
+    Day { day: 9, part1: day_9::part1, part2: day_9::part2 },
+    Day { day: 10, part1: day_10::part1, part2: day_10::part2 },
+    Day { day: 11, part1: day_11::part1, part2: day_11::part2 },
+    Day { day: 12, part1: day_12::part1, part2: day_12::part2 },
+    Day { day: 13, part1: day_13::part1, part2: day_13::part2 },
+    Day { day: 14, part1: day_14::part1, part2: day_14::part2 },
+    Day { day: 15, part1: day_15::part1, part2: day_15::part2 },
+    Day { day: 16, part1: day_16::part1, part2: day_16::part2 },
+    Day { day: 17, part1: day_17::part1, part2: day_17::part2 },
+    Day { day: 18, part1: day_18::part1, part2: day_18::part2 },
+    Day { day: 19, part1: day_19::part1, part2: day_19::part2 },
+    Day { day: 20, part1: day_20::part1, part2: day_20::part2 },
+    Day { day: 21, part1: day_21::part1, part2: day_21::part2 },
+];
+
+pub fn get(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+/// Another implementation of one part of a day, kept around to compare it with the
+/// one registered in `DAYS`.
+pub struct Alternative {
+    pub day: u8,
+    pub part: u8,
+    pub name: &'static str,
+    pub solver: Solver,
+}
+
+/// Registry of the alternative implementations, in order of day and part.
+#[rustfmt::skip]
+pub const ALTERNATIVES: &[Alternative] = &[
+    Alternative { day: 5, part: 2, name: "interval sets", solver: day_5::part2 },
+    Alternative { day: 5, part: 2, name: "split ranges", solver: day_5::part2_split },
+    Alternative { day: 12, part: 1, name: "memoised", solver: day_12::part1_memo },
+    Alternative { day: 14, part: 1, name: "lines", solver: day_14::part1_lines },
+    Alternative { day: 14, part: 2, name: "in place", solver: day_14::part2_in_place },
+    Alternative { day: 16, part: 2, name: "single thread", solver: day_16::part2_single_thread },
+];
+
+pub fn alternatives(day: u8, part: u8) -> impl Iterator<Item = &'static Alternative> {
+    ALTERNATIVES
+        .iter()
+        .filter(move |a| a.day == day && a.part == part)
+}
+
+/// Name given to the implementation registered in `DAYS`, as opposed to the
+/// alternative ones.
+pub const DEFAULT_IMPLEMENTATION: &str = "default";
+
+/// Every implementation of one part of a day, with its name, starting with the one
+/// registered in `DAYS`.
+pub fn implementations(day: &Day, part: u8) -> impl Iterator<Item = (&'static str, Solver)> {
+    let alternatives = alternatives(day.day, part).map(|a| (a.name, a.solver));
+    [(DEFAULT_IMPLEMENTATION, day.part(part))]
+        .into_iter()
+        .chain(alternatives)
+}
+
+/// Replays the simulation of a day on a screen, taking the puzzle input as text.
+pub type Animator = fn(&str, &mut dyn Screen) -> Result<(), ParseError>;
+
+pub struct Animation {
+    pub day: u8,
+    pub animate: Animator,
+}
+
+/// Registry of the days which can replay their simulation, in order.
+#[rustfmt::skip]
+pub const ANIMATIONS: &[Animation] = &[
+    Animation { day: 14, animate: day_14::animate },
+    Animation { day: 16, animate: day_16::animate },
+    Animation { day: 21, animate: day_21::animate },
+];
+
+pub fn animation(day: u8) -> Option<&'static Animation> {
+    ANIMATIONS.iter().find(|a| a.day == day)
+}
+
+/// Draws a puzzle state of a day to an image, taking the puzzle input as text.
+pub type Renderer = fn(&str) -> Result<Picture, ParseError>;
+
+pub struct Rendering {
+    pub day: u8,
+    pub render: Renderer,
+}
+
+/// Registry of the days which can draw their puzzle state, in order.
+#[rustfmt::skip]
+pub const RENDERINGS: &[Rendering] = &[
+    Rendering { day: 10, render: day_10::render },
+    Rendering { day: 16, render: day_16::render },
+    Rendering { day: 17, render: day_17::render },
+    Rendering { day: 18, render: day_18::render },
+];
+
+pub fn rendering(day: u8) -> Option<&'static Rendering> {
+    RENDERINGS.iter().find(|r| r.day == day)
+}
+
+/// Opens a session of commands to explore the parsed puzzle input of a day.
+pub type Explorer = fn(&str) -> Result<Box<dyn Session>, ParseError>;
+
+pub struct Exploration {
+    pub day: u8,
+    pub explore: Explorer,
+}
+
+/// Registry of the days whose parsed input can be explored, in order.
+#[rustfmt::skip]
+pub const EXPLORATIONS: &[Exploration] = &[
+    Exploration { day: 5, explore: day_5::explore },
+    Exploration { day: 19, explore: day_19::explore },
+    Exploration { day: 20, explore: day_20::explore },
+];
+
+pub fn exploration(day: u8) -> Option<&'static Exploration> {
+    EXPLORATIONS.iter().find(|e| e.day == day)
+}
+
+/// Writes a random puzzle input for a day, of a given size.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+pub struct Generation {
+    pub day: u8,
+    pub generate: Generator,
+    /// Size giving inputs about as large as the puzzle input.
+    pub size: usize,
+}
+
+/// Registry of the generators of random inputs, in order.
+#[rustfmt::skip]
+pub const GENERATORS: &[Generation] = &[
+    Generation { day: 1, generate: day_1::generate, size: 1000 },
+    Generation { day: 2, generate: day_2::generate, size: 100 },
+    Generation { day: 3, generate: day_3::generate, size: 140 },
+    Generation { day: 4, generate: day_4::generate, size: 200 },
+    Generation { day: 5, generate: day_5::generate, size: 30 },
+    Generation { day: 6, generate: day_6::generate, size: 4 },
+    Generation { day: 7, generate: day_7::generate, size: 1000 },
+    Generation { day: 8, generate: day_8::generate, size: 750 },
+    Generation { day: 9, generate: day_9::generate, size: 200 },
+    Generation { day: 10, generate: day_10::generate, size: 140 },
+    Generation { day: 11, generate: day_11::generate, size: 140 },
+    Generation { day: 12, generate: day_12::generate, size: 1000 },
+    Generation { day: 13, generate: day_13::generate, size: 100 },
+    Generation { day: 14, generate: day_14::generate, size: 100 },
+    Generation { day: 15, generate: day_15::generate, size: 4000 },
+    Generation { day: 16, generate: day_16::generate, size: 110 },
+    Generation { day: 17, generate: day_17::generate, size: 141 },
+    Generation { day: 18, generate: day_18::generate, size: 40 },
+    Generation { day: 19, generate: day_19::generate, size: 500 },
+    Generation { day: 20, generate: day_20::generate, size: 12 },
+    Generation { day: 21, generate: day_21::generate, size: 131 },
+];
+
+pub fn generator(day: u8) -> Option<&'static Generation> {
+    GENERATORS.iter().find(|g| g.day == day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_generated_inputs() {
+        assert_eq!(GENERATORS.len(), DAYS.len());
+        for generation in GENERATORS {
+            let day = get(generation.day).unwrap();
+            for seed in 0..3 {
+                let input = (generation.generate)(&mut Rng::new(seed), generation.size.min(10));
+                for part in [1, 2] {
+                    for (_, solver) in implementations(day, part) {
+                        assert!(
+                            solver(&input).is_ok(),
+                            "day {} part {} failed on the input of seed {}:\n{}",
+                            day.day,
+                            part,
+                            seed,
+                            input
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! JSON output of the answers, for `--format json`. Each answer is one JSON object
+//! on its own line.
+
+use std::time::Duration;
+
+use aoc_common::metrics::Metrics;
+use aoc_common::Answer;
+
+use crate::ledger::Status;
+
+/// Quote and escape `text` as a JSON string.
+pub fn string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// The answer to one part of a day, and how long it took to compute it.
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    pub elapsed: Duration,
+    /// Comparison with the ledger of the input, if it has one.
+    pub status: Option<Status>,
+    /// Counters of the work done by the solver.
+    pub metrics: Metrics,
+}
+
+fn answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Number(n) => n.to_string(),
+        Answer::Text(text) => string(text),
+    }
+}
+
+impl Record {
+    /// Format the record as `{"day":..,"part":..,"answer":..,"elapsed":..}`, where
+    /// `answer` is a number or a string depending on the puzzle, and `elapsed` is in
+    /// seconds. When the input has a ledger, `status` is "pass", "fail" or "new", and
+    /// `expected` gives the accepted answer of a failure. `metrics` holds the counters
+    /// of the solver, if it has any, like `{"states_expanded":12345}`.
+    pub fn to_json(&self) -> String {
+        let mut json = format!(
+            "{{\"day\":{},\"part\":{},\"answer\":{},\"elapsed\":{}",
+            self.day,
+            self.part,
+            answer(&self.answer),
+            self.elapsed.as_secs_f64()
+        );
+        if let Some(status) = &self.status {
+            json += &format!(",\"status\":{}", string(&status.name().to_lowercase()));
+            if let Status::Fail(expected) = status {
+                json += &format!(",\"expected\":{}", answer(expected));
+            }
+        }
+        if !self.metrics.is_empty() {
+            let metrics: Vec<_> = self
+                .metrics
+                .iter()
+                .map(|(name, value)| format!("{}:{}", string(name), value))
+                .collect();
+            json += &format!(",\"metrics\":{{{}}}", metrics.join(","));
+        }
+        json.push('}');
+        json
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_string() {
+        assert_eq!(string("abc"), "\"abc\"");
+        assert_eq!(string("a \"b\"\\\n"), "\"a \\\"b\\\"\\\\\\n\"");
+        assert_eq!(string("\u{1}"), "\"\\u0001\"");
+    }
+
+    #[test]
+    fn test_record() {
+        let mut record = Record {
+            day: 8,
+            part: 2,
+            answer: 17972669116327_u64.into(),
+            elapsed: Duration::from_millis(1500),
+            status: None,
+            metrics: vec![],
+        };
+        assert_eq!(
+            record.to_json(),
+            "{\"day\":8,\"part\":2,\"answer\":17972669116327,\"elapsed\":1.5}"
+        );
+        record.answer = "PZGZ".into();
+        assert_eq!(
+            record.to_json(),
+            "{\"day\":8,\"part\":2,\"answer\":\"PZGZ\",\"elapsed\":1.5}"
+        );
+        record.status = Some(Status::Fail("PZGC".into()));
+        assert_eq!(
+            record.to_json(),
+            "{\"day\":8,\"part\":2,\"answer\":\"PZGZ\",\"elapsed\":1.5,\"status\":\"fail\",\"expected\":\"PZGC\"}"
+        );
+        record.status = None;
+        record.metrics = vec![("states_expanded", 12), ("memo_hits", 3)];
+        assert_eq!(
+            record.to_json(),
+            "{\"day\":8,\"part\":2,\"answer\":\"PZGZ\",\"elapsed\":1.5,\"metrics\":{\"states_expanded\":12,\"memo_hits\":3}}"
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Ledger of the accepted answers for an input, stored next to it in a small TOML
+//! file, so that the runner can tell when a change alters an answer.
+//!
+//! For `17/input.txt`, the ledger is `17/input.answers.toml`:
+//!
+//! ```toml
+//! part1 = 102
+//! part2 = 94
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use aoc_common::Answer;
+
+/// How an answer compares with the one in the ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// Same answer as the accepted one.
+    Pass,
+    /// Different answer from the accepted one, which is given.
+    Fail(Answer),
+    /// No answer accepted yet for this part.
+    New,
+}
+
+impl Status {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Pass => "PASS",
+            Status::Fail(_) => "FAIL",
+            Status::New => "NEW",
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Fail(expected) => write!(f, "FAIL, expected {}", expected),
+            _ => f.write_str(self.name()),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Ledger {
+    answers: BTreeMap<u8, Answer>,
+}
+
+/// Path of the ledger for the input file at `input`.
+pub fn path_for(input: &Path) -> PathBuf {
+    let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+    input.with_file_name(format!("{}.answers.toml", stem))
+}
+
+fn parse_value(value: &str) -> Option<Answer> {
+    if let Some(text) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        let mut unescaped = String::new();
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next()? {
+                    c @ ('"' | '\\') => unescaped.push(c),
+                    _ => return None,
+                },
+                '"' => return None,
+                c => unescaped.push(c),
+            }
+        }
+        Some(Answer::Text(unescaped))
+    } else {
+        value.replace('_', "").parse().ok().map(Answer::Number)
+    }
+}
+
+fn format_value(answer: &Answer) -> String {
+    match answer {
+        Answer::Number(n) => n.to_string(),
+        Answer::Text(text) => format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\"")),
+    }
+}
+
+impl Ledger {
+    /// Parse a ledger: `partN = <answer>` lines, where the answer is an integer or a
+    /// string, along with comments and blank lines.
+    pub fn parse(text: &str) -> Result<Ledger, String> {
+        let mut ledger = Ledger::default();
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |message: &str| format!("line {}: {}", idx + 1, message);
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| invalid("expected 'part<N> = <answer>'"))?;
+            let part = key
+                .trim()
+                .strip_prefix("part")
+                .and_then(|part| part.parse().ok())
+                .ok_or_else(|| invalid("expected a key like 'part1'"))?;
+            let answer = parse_value(value.trim())
+                .ok_or_else(|| invalid("expected an integer or a string"))?;
+            ledger.answers.insert(part, answer);
+        }
+        Ok(ledger)
+    }
+
+    /// Read the ledger at `path`. A missing file is an empty ledger.
+    pub fn load(path: &Path) -> Result<Ledger, String> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Ledger::parse(&text).map_err(|e| format!("{}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Ledger::default()),
+            Err(e) => Err(format!("could not read {}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, self.to_toml())
+            .map_err(|e| format!("could not write {}: {}", path.display(), e))
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut text = "# Accepted answers for this input, checked by `aoc run`\n".to_string();
+        for (part, answer) in &self.answers {
+            text += &format!("part{} = {}\n", part, format_value(answer));
+        }
+        text
+    }
+
+    pub fn check(&self, part: u8, answer: &Answer) -> Status {
+        match self.answers.get(&part) {
+            None => Status::New,
+            Some(expected) if expected == answer => Status::Pass,
+            Some(expected) => Status::Fail(expected.clone()),
+        }
+    }
+
+    pub fn accept(&mut self, part: u8, answer: Answer) {
+        self.answers.insert(part, answer);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_path_for() {
+        assert_eq!(
+            path_for(Path::new("17/input.txt")),
+            PathBuf::from("17/input.answers.toml")
+        );
+        assert_eq!(
+            path_for(Path::new("inputs/alice/8")),
+            PathBuf::from("inputs/alice/8.answers.toml")
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut ledger = Ledger::default();
+        ledger.accept(2, "say \"hi\"\\".into());
+        ledger.accept(1, 17972669116327_u64.into());
+        let text = ledger.to_toml();
+        assert!(text.contains("part1 = 17972669116327\npart2 = \"say \\\"hi\\\"\\\\\"\n"));
+        assert_eq!(Ledger::parse(&text), Ok(ledger));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Ledger::parse("part1 102").is_err());
+        assert!(Ledger::parse("answer = 102").is_err());
+        assert!(Ledger::parse("part1 = 1O2").is_err());
+        assert!(Ledger::parse("part1 = \"a\"b\"").is_err());
+        assert_eq!(
+            Ledger::parse("# comment\n\npart1 = 1_000")
+                .unwrap()
+                .check(1, &1000.into()),
+            Status::Pass
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let ledger = Ledger::parse("part1 = 102").unwrap();
+        assert_eq!(ledger.check(1, &102.into()), Status::Pass);
+        assert_eq!(ledger.check(1, &101.into()), Status::Fail(102.into()));
+        assert_eq!(ledger.check(2, &94.into()), Status::New);
+        assert_eq!(Status::Fail(102.into()).to_string(), "FAIL, expected 102");
+    }
+}
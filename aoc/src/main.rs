@@ -0,0 +1,508 @@
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use aoc_common::animation::{Player, PlayerOptions};
+use aoc_common::input::{InputSource, INPUT_ENV_VAR};
+use aoc_common::metrics::{self, Metrics};
+use aoc_common::random::Rng;
+use aoc_common::ParseError;
+
+mod alloc;
+mod batch;
+mod bench;
+mod cli;
+mod compare;
+mod days;
+mod json;
+mod ledger;
+mod panics;
+mod scaffold;
+
+use bench::Measurement;
+use cli::{BenchOptions, Command, Days, Format};
+use ledger::{Ledger, Status};
+
+#[global_allocator]
+static ALLOCATOR: alloc::CountingAlloc = alloc::CountingAlloc;
+
+fn input_source(day: u8, input: Option<&str>) -> InputSource {
+    let default = PathBuf::from(day.to_string()).join("input.txt");
+    InputSource::resolve(input, default, day)
+}
+
+fn read_input(day: &days::Day, input: Option<&str>) -> Result<String, String> {
+    input_source(day.day, input)
+        .read()
+        .map_err(|e| e.to_string())
+}
+
+fn invalid_input(day: u8, part: u8, error: ParseError, input: &str) -> String {
+    format!(
+        "day {} part {}: invalid input, {}",
+        day,
+        part,
+        error.report(input)
+    )
+}
+
+fn parts(part: Option<u8>) -> Vec<u8> {
+    match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    }
+}
+
+/// The days to go through, and the input argument to use for them.
+fn select_days(
+    days: Days,
+    input: Option<String>,
+) -> Result<(Vec<&'static days::Day>, Option<String>), String> {
+    match days {
+        Days::One(n) => {
+            let day = days::get(n).ok_or(format!("day {} is not solved yet", n))?;
+            Ok((vec![day], input))
+        }
+        Days::All => {
+            let input = input.or_else(|| std::env::var(INPUT_ENV_VAR).ok());
+            let input = input.filter(|input| !input.is_empty());
+            if let Some(input) = &input {
+                cli::check_input_for_all_days(input)
+                    .map_err(|e| format!("{} (from {})", e, INPUT_ENV_VAR))?;
+            }
+            Ok((days::DAYS.iter().collect(), input))
+        }
+    }
+}
+
+/// Run the given parts of a day, and return how many answers differ from the ledger.
+fn run_day(
+    day: &days::Day,
+    part: Option<u8>,
+    input: Option<&str>,
+    format: Format,
+    accept: bool,
+    render: Option<&str>,
+) -> Result<usize, String> {
+    let rendering = match render {
+        Some(_) => Some(days::rendering(day.day).ok_or_else(|| {
+            not_supported(day.day, "drawing", days::RENDERINGS.iter().map(|r| r.day))
+        })?),
+        None => None,
+    };
+    let source = input_source(day.day, input);
+    // Only input files have a ledger, next to them
+    let ledger_path = match &source {
+        InputSource::File(path) => Some(ledger::path_for(path)),
+        InputSource::Stdin if accept => {
+            return Err("--accept needs an input file to store the answers next to".into())
+        }
+        InputSource::Stdin => None,
+    };
+    let mut ledger = ledger_path.as_deref().map(Ledger::load).transpose()?;
+    let input = source.read().map_err(|e| e.to_string())?;
+
+    let mut failures = 0;
+    for part in parts(part) {
+        // Only count what this solver does
+        metrics::take();
+        let now = Instant::now();
+        let answer = panics::catch(|| (day.part(part))(&input))
+            .map_err(|e| format!("day {} part {}: {}", day.day, part, e))?
+            .map_err(|e| invalid_input(day.day, part, e, &input))?;
+        let elapsed = now.elapsed();
+        let metrics = metrics::take();
+
+        let status = ledger.as_ref().map(|ledger| ledger.check(part, &answer));
+        if let Some(ledger) = ledger.as_mut().filter(|_| accept) {
+            ledger.accept(part, answer.clone());
+        } else if let Some(Status::Fail(_)) = status {
+            failures += 1;
+        }
+
+        match format {
+            Format::Text => {
+                let status = match &status {
+                    Some(status) if accept && *status != Status::Pass => {
+                        format!(" ({}, accepted)", status)
+                    }
+                    Some(status) => format!(" ({})", status),
+                    None => String::new(),
+                };
+                println!(
+                    "Day {} part {}: {}{}{}",
+                    day.day,
+                    part,
+                    answer,
+                    status,
+                    format_metrics(&metrics)
+                );
+            }
+            Format::Json => {
+                let record = json::Record {
+                    day: day.day,
+                    part,
+                    answer,
+                    elapsed,
+                    status,
+                    metrics,
+                };
+                println!("{}", record.to_json());
+            }
+        }
+    }
+
+    if let (Some(ledger), Some(path)) = (&ledger, &ledger_path) {
+        if accept {
+            ledger.save(path)?;
+        }
+    }
+
+    if let (Some(path), Some(rendering)) = (render, rendering) {
+        let picture = (rendering.render)(&input)
+            .map_err(|e| format!("day {}: invalid input, {}", day.day, e.report(&input)))?;
+        picture
+            .save(Path::new(path))
+            .map_err(|e| format!("could not save {}: {}", path, e))?;
+    }
+    Ok(failures)
+}
+
+/// The counters of a solver, as ` [name=value, ...]` after its answer, if it has any.
+fn format_metrics(metrics: &Metrics) -> String {
+    if metrics.is_empty() {
+        return String::new();
+    }
+    let metrics: Vec<_> = metrics
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect();
+    format!(" [{}]", metrics.join(", "))
+}
+
+fn bench(
+    days: &[&days::Day],
+    part: Option<u8>,
+    input: Option<&str>,
+    options: &BenchOptions,
+) -> Result<(), String> {
+    let baseline = match &options.baseline {
+        Some(path) => {
+            let text = std::fs::read_to_string(path)
+                .map_err(|e| format!("could not read baseline {}: {}", path, e))?;
+            Some(bench::parse_baseline(&text).map_err(|e| format!("{}: {}", path, e))?)
+        }
+        None => None,
+    };
+
+    println!("{}", bench::header());
+    let mut measurements = vec![];
+    let mut slower = 0;
+    for day in days {
+        let input = read_input(day, input)?;
+        for part in parts(part) {
+            for (implementation, solver) in days::implementations(day, part) {
+                let (time, stats) = bench::measure(solver, &input, options.runs)
+                    .map_err(|e| invalid_input(day.day, part, e, &input))?;
+                let measurement = Measurement {
+                    day: day.day,
+                    part,
+                    implementation: implementation.to_string(),
+                    time,
+                    allocations: stats.allocations,
+                    peak: stats.peak,
+                };
+                let (line, is_slower) =
+                    bench::row(&measurement, baseline.as_deref(), options.threshold);
+                println!("{}", line);
+                slower += is_slower as usize;
+                measurements.push(measurement);
+            }
+        }
+    }
+
+    if let Some(path) = &options.save {
+        std::fs::write(path, bench::format_baseline(&measurements))
+            .map_err(|e| format!("could not save baseline to {}: {}", path, e))?;
+    }
+    if slower > 0 {
+        return Err(format!(
+            "{} solver(s) more than {}% slower than in the baseline",
+            slower, options.threshold
+        ));
+    }
+    Ok(())
+}
+
+/// Error for a day without a `feature`, which the days in `supported` have.
+fn not_supported(day: u8, feature: &str, supported: impl Iterator<Item = u8>) -> String {
+    let mut supported: Vec<_> = supported.map(|day| day.to_string()).collect();
+    supported.dedup();
+    format!(
+        "day {} has no {}, try one of days {}",
+        day,
+        feature,
+        supported.join(", ")
+    )
+}
+
+fn animate(day: u8, input: Option<&str>, fps: u32, color: bool) -> Result<(), String> {
+    let animation = days::animation(day)
+        .ok_or_else(|| not_supported(day, "animation", days::ANIMATIONS.iter().map(|a| a.day)))?;
+    let source = input_source(day, input);
+    let input = source.read().map_err(|e| e.to_string())?;
+    let options = PlayerOptions {
+        fps,
+        color: color && io::stdout().is_terminal(),
+        // The keys are read from stdin, unless the input comes from there
+        keys: source != InputSource::Stdin,
+    };
+    let mut player = Player::new(options);
+    (animation.animate)(&input, &mut player)
+        .map_err(|e| format!("day {}: invalid input, {}", day, e.report(&input)))
+}
+
+fn generate(day: u8, size: Option<usize>, seed: Option<u64>) -> Result<(), String> {
+    let generation = days::generator(day).ok_or_else(|| {
+        not_supported(
+            day,
+            "input generator",
+            days::GENERATORS.iter().map(|g| g.day),
+        )
+    })?;
+    let seed = seed.unwrap_or_else(|| {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos() as u64);
+        eprintln!("seed {}", seed);
+        seed
+    });
+    let mut rng = Rng::new(seed);
+    print!(
+        "{}",
+        (generation.generate)(&mut rng, size.unwrap_or(generation.size))
+    );
+    Ok(())
+}
+
+fn compare(days: Days, inputs: u64, size: Option<usize>) -> Result<(), String> {
+    let days = match days {
+        Days::One(n) => {
+            let day = days::get(n).ok_or(format!("day {} is not solved yet", n))?;
+            if compare::compared_parts(day).is_empty() {
+                let supported = days::ALTERNATIVES.iter().map(|a| a.day);
+                return Err(not_supported(n, "alternative implementation", supported));
+            }
+            vec![day]
+        }
+        Days::All => days::DAYS.iter().collect(),
+    };
+
+    let mut disagreements = 0;
+    for day in days {
+        let parts = compare::compared_parts(day);
+        if parts.is_empty() {
+            continue;
+        }
+        let mut cases = compare::examples(Path::new(&day.day.to_string()))?;
+        cases.extend(compare::generated(day.day, size, 0..inputs));
+        for part in parts {
+            let mut agreed = 0;
+            for case in &cases {
+                match compare::compare(day, part, case) {
+                    Some(disagreement) => {
+                        println!("{}", disagreement);
+                        disagreements += 1;
+                    }
+                    None => agreed += 1,
+                }
+            }
+            let names: Vec<_> = days::implementations(day, part)
+                .map(|(name, _)| name)
+                .collect();
+            println!(
+                "Day {} part {}: {} agree on {} of {} inputs",
+                day.day,
+                part,
+                names.join(", "),
+                agreed,
+                cases.len()
+            );
+        }
+    }
+    if disagreements > 0 {
+        return Err(format!(
+            "{} input(s) where the implementations disagree",
+            disagreements
+        ));
+    }
+    Ok(())
+}
+
+fn new(day: u8) -> Result<(), String> {
+    let changes = scaffold::plan(Path::new(""), day)?;
+    scaffold::apply(&changes)?;
+    for change in &changes {
+        let action = if change.created { "created" } else { "updated" };
+        println!("{} {}", action, change.path.display());
+    }
+    println!(
+        "Put the example of the puzzle in {0}/example.txt and its answers in the tests of \
+         {0}/src/lib.rs, and the puzzle input in {0}/input.txt",
+        day
+    );
+    Ok(())
+}
+
+fn repl(day: u8, input: Option<&str>) -> Result<(), String> {
+    let exploration = days::exploration(day)
+        .ok_or_else(|| not_supported(day, "repl", days::EXPLORATIONS.iter().map(|e| e.day)))?;
+    let source = input_source(day, input);
+    if source == InputSource::Stdin {
+        return Err("repl reads its commands from stdin, give it the input as a file".into());
+    }
+    let input = source.read().map_err(|e| e.to_string())?;
+    let mut session = (exploration.explore)(&input)
+        .map_err(|e| format!("day {}: invalid input, {}", day, e.report(&input)))?;
+    if io::stdin().is_terminal() {
+        println!("Exploring day {}, type help for the commands", day);
+    }
+    aoc_common::repl::run(session.as_mut(), &format!("day {}> ", day));
+    Ok(())
+}
+
+fn batch(dir: &str, part: Option<u8>, jobs: Option<usize>) -> Result<(), String> {
+    let sets = batch::sets(Path::new(dir))?;
+    let days: Vec<_> = days::DAYS.iter().collect();
+    let jobs_to_run = batch::jobs(&sets, &days, &parts(part));
+    let threads = jobs.unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()));
+    let now = Instant::now();
+    let outcomes: Vec<_> = batch::map(&jobs_to_run, threads, batch::solve)
+        .into_iter()
+        .map(|result| result.unwrap_or_else(|e| Err(format!("panicked, {}", e))))
+        .collect();
+    let (table, summary) = batch::matrix(&sets, &jobs_to_run, &outcomes);
+    print!("{}", table);
+    println!(
+        "\n{} jobs on {} sets in {:.2} s, on {} thread{}",
+        jobs_to_run.len(),
+        sets.len(),
+        now.elapsed().as_secs_f64(),
+        threads,
+        if threads == 1 { "" } else { "s" }
+    );
+    if summary.failed > 0 || summary.differing > 0 {
+        return Err(format!(
+            "{} job(s) failed, {} answer(s) differ from the ledger",
+            summary.failed, summary.differing
+        ));
+    }
+    Ok(())
+}
+
+fn run(command: Command) -> Result<(), String> {
+    match command {
+        Command::Run {
+            days,
+            part,
+            input,
+            format,
+            log,
+            accept,
+            render,
+        } => {
+            if let Some(level) = log {
+                aoc_common::log::set_level(level);
+            }
+            let (days, input) = select_days(days, input)?;
+            let mut failures = 0;
+            for day in days {
+                failures += run_day(
+                    day,
+                    part,
+                    input.as_deref(),
+                    format,
+                    accept,
+                    render.as_deref(),
+                )?;
+            }
+            if failures > 0 {
+                return Err(format!(
+                    "{} answer(s) differ from the ledger, run with --accept if they are right",
+                    failures
+                ));
+            }
+            Ok(())
+        }
+        Command::Bench {
+            days,
+            part,
+            input,
+            log,
+            options,
+        } => {
+            if let Some(level) = log {
+                aoc_common::log::set_level(level);
+            }
+            let (days, input) = select_days(days, input)?;
+            bench(&days, part, input.as_deref(), &options)
+        }
+        Command::Animate {
+            day,
+            input,
+            log,
+            fps,
+            color,
+        } => {
+            if let Some(level) = log {
+                aoc_common::log::set_level(level);
+            }
+            animate(day, input.as_deref(), fps, color)
+        }
+        Command::Generate { day, size, seed } => generate(day, size, seed),
+        Command::Compare { days, inputs, size } => compare(days, inputs, size),
+        Command::New { day } => new(day),
+        Command::Repl { day, input, log } => {
+            if let Some(level) = log {
+                aoc_common::log::set_level(level);
+            }
+            repl(day, input.as_deref())
+        }
+        Command::Batch {
+            dir,
+            part,
+            log,
+            jobs,
+        } => {
+            if let Some(level) = log {
+                aoc_common::log::set_level(level);
+            }
+            batch(&dir, part, jobs)
+        }
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            Ok(())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    panics::install_hook();
+    let command = match cli::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Panics of the solvers, like an arithmetic overflow in a build with overflow checks,
+//! caught and reported as errors, with where they happened.
+
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+
+thread_local! {
+    /// Whether a panic on this thread is caught by `catch`, which reports it.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// Where the last panic caught on this thread happened.
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Keep where the panics caught by `catch` happen, instead of printing them. Other
+/// panics are printed as usual.
+pub fn install_hook() {
+    let default = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if CATCHING.get() {
+            LOCATION.set(info.location().map(|location| location.to_string()));
+        } else {
+            default(info);
+        }
+    }));
+}
+
+/// Run `f`, or return why and where it panicked, like "attempt to multiply with
+/// overflow at 6/src/lib.rs:39:38".
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    let catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(catching);
+    result.map_err(|payload| {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => "panicked".to_string(),
+            },
+        };
+        match LOCATION.take() {
+            Some(location) => format!("{} at {}", message, location),
+            None => message,
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_catch() {
+        install_hook();
+        assert_eq!(catch(|| 1 + 1), Ok(2));
+        let overflow = catch(|| std::hint::black_box(u8::MAX) + 1).unwrap_err();
+        let expected = format!("attempt to add with overflow at {}:", file!());
+        assert!(overflow.starts_with(&expected), "{}", overflow);
+        let error = catch(|| panic!("no path {}", 2)).unwrap_err();
+        assert!(error.starts_with("no path 2 at "), "{}", error);
+    }
+}
@@ -0,0 +1,327 @@
+//! Scaffolding of a new day: its crate, laid out like the other days, with stubs for
+//! both parts and for the input generator, and its registration in the workspace and
+//! in the runner.
+
+use std::path::{Path, PathBuf};
+
+const CARGO_TOML: &str = "\
+[package]
+name = \"day-{day}\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+aoc-common = { path = \"../common\" }
+";
+
+const MAIN_RS: &str = "\
+use aoc_common::print_answer;
+
+fn main() {
+    let input = aoc_common::input::read_main_input({day});
+    print_answer(\"Part 1\", day_{day}::part1(&input), &input);
+    print_answer(\"Part 2\", day_{day}::part2(&input), &input);
+}
+";
+
+const LIB_RS: &str = "\
+use aoc_common::random::Rng;
+use aoc_common::{debug, Answer, ParseError};
+
+pub fn part1(input: &str) -> Result<Answer, ParseError> {
+    debug!(\"{} lines\", input.lines().count());
+    Ok(0.into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, ParseError> {
+    debug!(\"{} lines\", input.lines().count());
+    Ok(0.into())
+}
+
+/// Random puzzle input of `size` lines.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        input += &rng.range(1..=100_u32).to_string();
+        input.push('\\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{part1, part2};
+
+    #[test]
+    #[ignore = \"part 1 is not solved yet\"]
+    fn test_part1_example() {
+        assert_eq!(part1(include_str!(\"../example.txt\")), Ok(0.into()));
+    }
+
+    #[test]
+    #[ignore = \"part 2 is not solved yet\"]
+    fn test_part2_example() {
+        assert_eq!(part2(include_str!(\"../example.txt\")), Ok(0.into()));
+    }
+}
+";
+
+/// Longest line in the list of workspace members.
+const MEMBERS_WIDTH: usize = 64;
+
+/// A file to write for the new day.
+#[derive(Debug)]
+pub struct Change {
+    pub path: PathBuf,
+    pub contents: String,
+    /// Whether the file is new, rather than an update of an existing one.
+    pub created: bool,
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path.display(), e))
+}
+
+/// The number right after `prefix` in `line`, if there is one.
+fn number_after(line: &str, prefix: &str) -> Option<u8> {
+    let start = line.find(prefix)? + prefix.len();
+    let digits = line[start..].split(|c: char| !c.is_ascii_digit()).next()?;
+    digits.parse().ok()
+}
+
+/// Insert `entry` among the lines of `text` between the first one starting with
+/// `start` and the next one starting with `end`, or the end of `text`, keeping them in
+/// order of day: the day of a line is the number after `key`.
+fn insert_in_order(
+    text: &str,
+    (start, end): (&str, &str),
+    key: &str,
+    day: u8,
+    entry: String,
+) -> Result<String, String> {
+    let mut lines: Vec<String> = text.lines().map(String::from).collect();
+    let first = lines
+        .iter()
+        .position(|line| line.starts_with(start))
+        .ok_or(format!("could not find '{}'", start))?;
+    let last = (first + 1..lines.len())
+        .find(|&i| lines[i].starts_with(end))
+        .unwrap_or(lines.len());
+    let mut position = first + 1;
+    for (i, line) in lines.iter().enumerate().take(last).skip(first + 1) {
+        match number_after(line, key) {
+            Some(other) if other == day => return Err(format!("day {} is already there", day)),
+            Some(other) if other > day => break,
+            Some(_) => position = i + 1,
+            None => {}
+        }
+    }
+    lines.insert(position, entry);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Add the day to the members of the workspace manifest. The days come first, in
+/// order, followed by the other crates on a line of their own.
+fn add_member(manifest: &str, day: u8) -> Result<String, String> {
+    let start = manifest
+        .find("members = [")
+        .ok_or("could not find the workspace members")?
+        + "members = [".len();
+    let end = start + manifest[start..].find(']').ok_or("unterminated members")?;
+    let members: Vec<&str> = manifest[start..end]
+        .split(',')
+        .map(|member| member.trim().trim_matches('"'))
+        .filter(|member| !member.is_empty())
+        .collect();
+    let (mut days, others): (Vec<_>, Vec<_>) = members
+        .into_iter()
+        .partition(|member| member.parse::<u8>().is_ok());
+    let day = day.to_string();
+    if days.contains(&day.as_str()) {
+        return Err(format!("day {} is already a member of the workspace", day));
+    }
+    days.push(&day);
+    days.sort_by_key(|day| day.parse::<u8>().unwrap());
+
+    let mut list = String::new();
+    let mut line = String::from("   ");
+    for day in days {
+        let item = format!(" \"{}\",", day);
+        if line.len() + item.len() > MEMBERS_WIDTH {
+            list += &line;
+            list.push('\n');
+            line = String::from("   ");
+        }
+        line += &item;
+    }
+    list += &line;
+    list.push('\n');
+    let others: Vec<_> = others
+        .iter()
+        .map(|other| format!("\"{}\",", other))
+        .collect();
+    if !others.is_empty() {
+        list += &format!("    {}\n", others.join(" "));
+    }
+    Ok(format!(
+        "{}\n{}{}",
+        &manifest[..start],
+        list,
+        &manifest[end..]
+    ))
+}
+
+/// Everything to write to add `day` to the workspace at `root`, without writing it yet.
+pub fn plan(root: &Path, day: u8) -> Result<Vec<Change>, String> {
+    let workspace_path = root.join("Cargo.toml");
+    let workspace = read(&workspace_path)?;
+    if !workspace.contains("[workspace]") {
+        return Err(format!(
+            "{} is not the workspace manifest, run this from the root of the workspace",
+            workspace_path.display()
+        ));
+    }
+    let dir = root.join(day.to_string());
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    let runner_path = root.join("aoc").join("Cargo.toml");
+    let runner = insert_in_order(
+        &read(&runner_path)?,
+        ("[dependencies]", "["),
+        "day-",
+        day,
+        format!("day-{0} = {{ path = \"../{0}\" }}", day),
+    )?;
+    let registry_path = root.join("aoc").join("src").join("days.rs");
+    let registry = insert_in_order(
+        &read(&registry_path)?,
+        ("pub const DAYS", "];"),
+        "day: ",
+        day,
+        format!(
+            "    Day {{ day: {0}, part1: day_{0}::part1, part2: day_{0}::part2 }},",
+            day
+        ),
+    )?;
+    let registry = insert_in_order(
+        &registry,
+        ("pub const GENERATORS", "];"),
+        "day: ",
+        day,
+        format!(
+            "    Generation {{ day: {0}, generate: day_{0}::generate, size: 100 }},",
+            day
+        ),
+    )?;
+
+    let template = |template: &str| template.replace("{day}", &day.to_string());
+    let created = |path: PathBuf, contents: String| Change {
+        path,
+        contents,
+        created: true,
+    };
+    let updated = |path: PathBuf, contents: String| Change {
+        path,
+        contents,
+        created: false,
+    };
+    Ok(vec![
+        created(dir.join("Cargo.toml"), template(CARGO_TOML)),
+        created(dir.join("src").join("main.rs"), template(MAIN_RS)),
+        created(dir.join("src").join("lib.rs"), template(LIB_RS)),
+        created(dir.join("example.txt"), String::new()),
+        updated(workspace_path, add_member(&workspace, day)?),
+        updated(runner_path, runner),
+        updated(registry_path, registry),
+    ])
+}
+
+/// Write the changes planned by `plan`.
+pub fn apply(changes: &[Change]) -> Result<(), String> {
+    for change in changes {
+        if let Some(dir) = change.path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("could not create {}: {}", dir.display(), e))?;
+        }
+        std::fs::write(&change.path, &change.contents)
+            .map_err(|e| format!("could not write {}: {}", change.path.display(), e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_add_member() {
+        let manifest = "\
+[workspace]
+resolver = \"2\"
+members = [
+    \"1\", \"2\", \"3\", \"4\", \"5\", \"6\", \"7\", \"8\", \"9\", \"10\", \"11\",
+    \"12\", \"13\", \"14\", \"15\", \"16\", \"17\", \"18\", \"19\", \"20\", \"21\",
+    \"aoc\", \"common\",
+]
+";
+        assert_eq!(
+            add_member(manifest, 22).unwrap(),
+            manifest.replace("\"21\",\n", "\"21\",\n    \"22\",\n")
+        );
+        assert_eq!(
+            add_member(&add_member(manifest, 22).unwrap(), 23).unwrap(),
+            manifest.replace("\"21\",\n", "\"21\",\n    \"22\", \"23\",\n")
+        );
+        assert!(add_member(manifest, 5).is_err());
+    }
+
+    #[test]
+    fn test_insert_in_order() {
+        let registry = "\
+pub const DAYS: &[Day] = &[
+    Day { day: 1, part1: day_1::part1, part2: day_1::part2 },
+    Day { day: 3, part1: day_3::part1, part2: day_3::part2 },
+];
+";
+        let entry = |day| format!("    Day {{ day: {} }},", day);
+        let bounds = ("pub const DAYS", "];");
+        let inserted = insert_in_order(registry, bounds, "day: ", 2, entry(2)).unwrap();
+        assert_eq!(inserted.lines().nth(2), Some("    Day { day: 2 },"));
+        let inserted = insert_in_order(registry, bounds, "day: ", 12, entry(12)).unwrap();
+        assert_eq!(inserted.lines().nth(3), Some("    Day { day: 12 },"));
+        assert!(insert_in_order(registry, bounds, "day: ", 3, entry(3)).is_err());
+    }
+
+    #[test]
+    fn test_plan() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let changes = plan(&root, 25).unwrap();
+        let lib = changes
+            .iter()
+            .find(|change| change.path.ends_with("25/src/lib.rs"))
+            .unwrap();
+        assert!(lib.created);
+        let main = changes
+            .iter()
+            .find(|change| change.path.ends_with("25/src/main.rs"))
+            .unwrap();
+        assert!(main.contents.contains("day_25::part1(&input)"));
+        let registry = changes
+            .iter()
+            .find(|change| change.path.ends_with("days.rs"))
+            .unwrap();
+        assert!(!registry.created);
+        assert!(registry
+            .contents
+            .contains("Day { day: 25, part1: day_25::part1"));
+        assert!(registry
+            .contents
+            .contains("Generation { day: 25, generate: day_25::generate"));
+
+        assert!(plan(&root, 5).is_err());
+    }
+}
@@ -0,0 +1,346 @@
+//! Step by step replay of the simulations in the terminal.
+//!
+//! A day draws each step of its simulation as a `Frame` of colored tiles and shows it
+//! on a `Screen`. The `Player` screen draws the frames over each other in the terminal,
+//! at a given number of frames per second, and reads the keyboard meanwhile:
+//!
+//! - space: pause or resume
+//! - n: show the next frame, while paused
+//! - + and -: double or halve the speed
+//! - q (or Ctrl-C): stop
+//!
+//! The keys are read as soon as they are pressed by taking the terminal out of its
+//! line mode with `stty`, so they are only available on Unix-like systems.
+
+use std::fmt::Write as _;
+use std::io::{self, IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::grid::{Grid, Position};
+
+/// Colors of the tiles, drawn with the bright ANSI colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Color {
+    #[default]
+    Default,
+    Gray,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    /// Select Graphic Rendition parameter setting this foreground color.
+    fn code(self) -> u8 {
+        match self {
+            Color::Default => 39,
+            Color::Gray => 90,
+            Color::Red => 91,
+            Color::Green => 92,
+            Color::Yellow => 93,
+            Color::Blue => 94,
+            Color::Magenta => 95,
+            Color::Cyan => 96,
+            Color::White => 97,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub tile: char,
+    pub color: Color,
+}
+
+impl Cell {
+    pub const fn new(tile: char, color: Color) -> Self {
+        Cell { tile, color }
+    }
+}
+
+/// One step of a simulation, with a caption line below the tiles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub cells: Grid<Cell>,
+    pub caption: String,
+}
+
+impl Frame {
+    /// Draw `grid`, with `draw` giving the cell of each of its tiles.
+    pub fn from_grid<T>(grid: &Grid<T>, mut draw: impl FnMut(Position, &T) -> Cell) -> Self {
+        Frame {
+            cells: Grid::from_fn(grid.width(), grid.height(), |p| draw(p, &grid[p])),
+            caption: String::new(),
+        }
+    }
+
+    pub fn with_caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = caption.into();
+        self
+    }
+
+    /// The tiles and the caption as text, colored with ANSI escape codes if `color`
+    /// is set.
+    pub fn render(&self, color: bool) -> String {
+        let mut text = String::new();
+        for row in self.cells.rows() {
+            let mut current = Color::Default;
+            for cell in row {
+                if color && cell.color != current {
+                    current = cell.color;
+                    write!(text, "\x1b[{}m", current.code()).unwrap();
+                }
+                text.push(cell.tile);
+            }
+            if current != Color::Default {
+                write!(text, "\x1b[{}m", Color::Default.code()).unwrap();
+            }
+            text.push('\n');
+        }
+        text += &self.caption;
+        text
+    }
+}
+
+/// Where the days show the frames of their simulations.
+pub trait Screen {
+    /// Show the next frame. Returns `false` once the viewer asked to stop, and the
+    /// simulation should end.
+    fn show(&mut self, frame: &Frame) -> bool;
+}
+
+/// Keep all the frames, to check them in tests.
+impl Screen for Vec<Frame> {
+    fn show(&mut self, frame: &Frame) -> bool {
+        self.push(frame.clone());
+        true
+    }
+}
+
+/// The keys pressed on a terminal, read by a background thread.
+struct Keyboard {
+    keys: Receiver<u8>,
+}
+
+/// Call `stty` with `args` on the terminal of stdin.
+pub(crate) fn stty(args: &[&str]) -> bool {
+    Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+impl Keyboard {
+    /// Read the keys of the terminal on stdin, if it is one. Ctrl-C is read as a key
+    /// as well, so that the terminal can be restored before stopping.
+    fn open() -> Option<Keyboard> {
+        if !io::stdin().is_terminal() || !stty(&["-icanon", "-echo", "-isig", "min", "1"]) {
+            return None;
+        }
+        let (sender, keys) = mpsc::channel();
+        thread::spawn(move || {
+            for key in io::stdin().lock().bytes() {
+                if key.ok().is_none_or(|key| sender.send(key).is_err()) {
+                    break;
+                }
+            }
+        });
+        Some(Keyboard { keys })
+    }
+}
+
+impl Drop for Keyboard {
+    fn drop(&mut self) {
+        stty(&["icanon", "echo", "isig"]);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlayerOptions {
+    pub fps: u32,
+    /// Draw the tiles in color.
+    pub color: bool,
+    /// Read the keyboard to control the replay.
+    pub keys: bool,
+}
+
+/// Replay the frames in the terminal, on stdout.
+pub struct Player {
+    fps: u32,
+    color: bool,
+    paused: bool,
+    stopped: bool,
+    keyboard: Option<Keyboard>,
+    frames: usize,
+    /// The last frame drawn, to draw it again when the status changes.
+    last: String,
+}
+
+/// Fastest speed, in frames per second: a terminal can hardly draw more.
+const MAX_FPS: u32 = 1000;
+
+impl Player {
+    pub fn new(options: PlayerOptions) -> Self {
+        Player {
+            fps: options.fps.clamp(1, MAX_FPS),
+            color: options.color,
+            paused: false,
+            stopped: false,
+            keyboard: options.keys.then(Keyboard::open).flatten(),
+            frames: 0,
+            last: String::new(),
+        }
+    }
+
+    /// React to a key. Returns `true` to move on to the next frame while paused.
+    fn press(&mut self, key: u8) -> bool {
+        match key {
+            b' ' => self.paused = !self.paused,
+            b'n' => return true,
+            b'+' | b'=' => self.fps = (self.fps * 2).min(MAX_FPS),
+            b'-' => self.fps = (self.fps / 2).max(1),
+            // Ctrl-C
+            b'q' | 3 => self.stopped = true,
+            _ => {}
+        }
+        false
+    }
+
+    fn status(&self) -> String {
+        let mut status = format!("frame {}, {} fps", self.frames, self.fps);
+        if self.paused {
+            status += ", paused";
+        }
+        if self.keyboard.is_some() {
+            status += "  (space: pause, n: next, +/-: speed, q: quit)";
+        }
+        status
+    }
+
+    fn draw(&self) {
+        let mut stdout = io::stdout().lock();
+        // Move to the top left, and clear what is left of the previous lines
+        let screen = self.last.replace('\n', "\x1b[K\n");
+        let _ = write!(stdout, "\x1b[H{}\x1b[K\n{}\x1b[K", screen, self.status());
+        let _ = stdout.flush();
+    }
+
+    /// Wait until it is time for the next frame, or until a key asks for it.
+    fn wait(&mut self) {
+        let next_at = Instant::now() + Duration::from_secs(1) / self.fps;
+        loop {
+            if self.stopped {
+                return;
+            }
+            let Some(keyboard) = &self.keyboard else {
+                thread::sleep(next_at.saturating_duration_since(Instant::now()));
+                return;
+            };
+            let key = if self.paused {
+                keyboard
+                    .keys
+                    .recv()
+                    .map_err(|_| RecvTimeoutError::Disconnected)
+            } else {
+                keyboard
+                    .keys
+                    .recv_timeout(next_at.saturating_duration_since(Instant::now()))
+            };
+            match key {
+                Ok(key) => {
+                    let next = self.press(key);
+                    self.draw();
+                    if next {
+                        return;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => return,
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.keyboard = None;
+                    self.paused = false;
+                }
+            }
+        }
+    }
+}
+
+impl Screen for Player {
+    fn show(&mut self, frame: &Frame) -> bool {
+        if self.stopped {
+            return false;
+        }
+        if self.frames == 0 {
+            // Clear the terminal and hide the cursor
+            print!("\x1b[2J\x1b[?25l");
+        }
+        self.frames += 1;
+        self.last = frame.render(self.color);
+        self.draw();
+        self.wait();
+        !self.stopped
+    }
+}
+
+impl Drop for Player {
+    fn drop(&mut self) {
+        if self.frames > 0 {
+            // Show the cursor again, below the last frame
+            println!("\x1b[?25h");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn frame() -> Frame {
+        let grid = Grid::parse("#.\n.#", Some).unwrap();
+        Frame::from_grid(&grid, |(x, _), &tile| {
+            Cell::new(tile, if x == 0 { Color::Red } else { Color::Default })
+        })
+        .with_caption("step 1")
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!(frame().render(false), "#.\n.#\nstep 1");
+        assert_eq!(
+            frame().render(true),
+            "\x1b[91m#\x1b[39m.\n\x1b[91m.\x1b[39m#\nstep 1"
+        );
+    }
+
+    #[test]
+    fn test_keys() {
+        let options = PlayerOptions {
+            fps: 10,
+            color: false,
+            keys: false,
+        };
+        let mut player = Player::new(options);
+        player.press(b'+');
+        assert_eq!(player.fps, 20);
+        for _ in 0..10 {
+            player.press(b'-');
+        }
+        assert_eq!(player.fps, 1);
+        assert!(!player.press(b' '));
+        assert!(player.paused);
+        assert!(player.press(b'n'));
+        assert!(player.status().ends_with("paused"));
+        player.press(b'q');
+        assert!(!player.show(&frame()));
+        let mut frames = vec![];
+        assert!(frames.show(&frame()));
+        assert_eq!(frames, [frame()]);
+    }
+}
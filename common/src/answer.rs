@@ -0,0 +1,79 @@
+use std::fmt;
+
+use crate::ParseError;
+
+/// The answer to one part of a puzzle.
+///
+/// Most puzzles have a numeric answer. Integers of any width convert into
+/// `Answer::Number`, so two answers compare equal regardless of the integer
+/// type that the solver used to compute them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Number(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Print the answer to one part in a day's standalone binary, or report why the
+/// input could not be parsed and exit.
+pub fn print_answer(name: &str, answer: Result<Answer, ParseError>, input: &str) {
+    match answer {
+        Ok(answer) => println!("{}: {}", name, answer),
+        Err(e) => {
+            eprintln!("error: {}", e.report(input));
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Answer;
+
+    #[test]
+    fn test_integer_widths_compare_equal() {
+        assert_eq!(Answer::from(142_u32), Answer::from(142_i64));
+        assert_eq!(Answer::from(142_usize), 142.into());
+        assert_ne!(Answer::from(-1), Answer::from(u64::MAX));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(281_u64).to_string(), "281");
+        assert_eq!(Answer::from("LRL").to_string(), "LRL");
+    }
+}
@@ -0,0 +1,200 @@
+//! Cycle detection for state machines stepped over and over, to find the state after
+//! a huge number of steps without running them all.
+//!
+//! The states are stepped in place. `find_cycle` and `advance` remember a
+//! fingerprint of every state seen in a hash map, which can be the state itself or
+//! any part of it that determines the following states. `brent` only compares
+//! states, and keeps two of them in memory.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// States repeating forever: the state after `start + length` steps is the same as
+/// after `start` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Steps before entering the cycle.
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Smallest number of steps giving the same state as `n` steps.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Step `state` until it reaches a fingerprint seen before, or `limit` steps.
+/// Returns the cycle found, if any, and the number of steps made.
+fn detect<S, K: Hash + Eq>(
+    state: &mut S,
+    limit: usize,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+) -> (Option<Cycle>, usize) {
+    let mut seen = HashMap::new();
+    for steps in 0..limit {
+        match seen.entry(key(state)) {
+            Entry::Occupied(entry) => {
+                let start = *entry.get();
+                let cycle = Cycle {
+                    start,
+                    length: steps - start,
+                };
+                return (Some(cycle), steps);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(steps);
+            }
+        }
+        step(state);
+    }
+    (None, limit)
+}
+
+/// Step `state` until its fingerprint `key` repeats, and return the cycle. `state` is
+/// left after `start + length` steps, in the same state as after `start` steps.
+///
+/// Never returns if the states do not repeat.
+pub fn find_cycle<S, K: Hash + Eq>(
+    state: &mut S,
+    step: impl FnMut(&mut S),
+    key: impl FnMut(&S) -> K,
+) -> Cycle {
+    detect(state, usize::MAX, step, key).0.unwrap()
+}
+
+/// Move `state` `n` steps forward, skipping the whole cycles once its fingerprint
+/// `key` repeats. Returns the cycle, if one was found within `n` steps.
+pub fn advance<S, K: Hash + Eq>(
+    state: &mut S,
+    n: usize,
+    mut step: impl FnMut(&mut S),
+    key: impl FnMut(&S) -> K,
+) -> Option<Cycle> {
+    let (cycle, steps) = detect(state, n, &mut step, key);
+    if let Some(cycle) = cycle {
+        for _ in 0..(n - steps) % cycle.length {
+            step(state);
+        }
+    }
+    cycle
+}
+
+/// Brent's cycle detection, for states that are too large to remember, or not
+/// hashable. It steps the states about three times as much as `find_cycle`.
+///
+/// Never returns if the states do not repeat.
+pub fn brent<S: Clone + Eq>(initial: &S, mut step: impl FnMut(&mut S)) -> Cycle {
+    // Find the length: the hare moves ahead, and the tortoise teleports to it each
+    // time the number of steps between them reaches a power of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    step(&mut hare);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        step(&mut hare);
+        length += 1;
+    }
+
+    // Find the start: with the hare a whole cycle ahead, both meet at its start
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..length {
+        step(&mut hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        step(&mut tortoise);
+        step(&mut hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// 3, 10, 5, 16, 8, 4, 2, 1, 4, 2, 1, ...
+    fn collatz(n: &mut u64) {
+        *n = if n.is_multiple_of(2) {
+            *n / 2
+        } else {
+            3 * *n + 1
+        };
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let mut n = 3;
+        let cycle = find_cycle(&mut n, collatz, |&n| n);
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 5,
+                length: 3
+            }
+        );
+        assert_eq!(n, 4);
+        assert_eq!(brent(&3, collatz), cycle);
+        assert_eq!(
+            brent(&1, collatz),
+            Cycle {
+                start: 0,
+                length: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_reduce() {
+        let cycle = Cycle {
+            start: 5,
+            length: 3,
+        };
+        assert_eq!(cycle.reduce(2), 2);
+        assert_eq!(cycle.reduce(5), 5);
+        assert_eq!(cycle.reduce(9), 6);
+        assert_eq!(cycle.reduce(1_000_000_000), 7);
+    }
+
+    #[test]
+    fn test_advance() {
+        let cycle = Cycle {
+            start: 5,
+            length: 3,
+        };
+        for n in [0, 4, 8, 9, 1_000_000_000] {
+            let mut expected = 3;
+            for _ in 0..cycle.reduce(n) {
+                collatz(&mut expected);
+            }
+            let mut state = 3;
+            advance(&mut state, n, collatz, |&n| n);
+            assert_eq!(state, expected, "after {} steps", n);
+        }
+        // The fingerprint can be any part of the state that determines the next ones
+        let mut state = (3, 0);
+        let step = |(n, steps): &mut (u64, usize)| {
+            collatz(n);
+            *steps += 1;
+        };
+        assert_eq!(advance(&mut state, 11, step, |&(n, _)| n), Some(cycle));
+        // Detected after 8 steps, and 11 steps are the same as 8
+        assert_eq!(state, (4, 8));
+        assert_eq!(advance(&mut 3, 4, collatz, |&n| n), None);
+    }
+}
@@ -0,0 +1,147 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::grid::Grid;
+
+/// An error in the puzzle input, located by its line and column (both starting at 1).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Error about `token`, which must be a slice of `text`, as obtained by splitting
+    /// or trimming it. The position is relative to the start of `text`: when `text`
+    /// is a single line of the input, use `below` to get the position in the input.
+    ///
+    /// An empty slice at the end of `text` points right after its last character,
+    /// for input that ends too early.
+    pub fn at(text: &str, token: &str, message: impl Into<String>) -> Self {
+        let start = text.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| offset + token.len() <= text.len())
+            .unwrap_or(0);
+        let before = &text[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let column = before[line_start..].chars().count() + 1;
+        ParseError::new(line, column, message)
+    }
+
+    /// Move the error `lines` lines down, for errors found while parsing one line
+    /// (or one block) of the input on its own.
+    pub fn below(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+
+    /// Describe the error along with the offending line of `input`, like:
+    ///
+    /// ```text
+    /// line 2, column 4: unknown direction 'X'
+    ///   2 | X 6 (#70c710)
+    ///     | ^
+    /// ```
+    pub fn report(&self, input: &str) -> String {
+        let mut report = self.to_string();
+        if let Some(text) = self.line.checked_sub(1).and_then(|i| input.lines().nth(i)) {
+            let number = self.line.to_string();
+            let padding = " ".repeat(number.len());
+            let caret = " ".repeat(self.column.saturating_sub(1));
+            report += &format!("\n  {} | {}\n  {} | {}^", number, text, padding, caret);
+        }
+        report
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse `token`, a slice of `text`, into a number.
+pub fn parse_number<T: FromStr>(text: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(text, token, format!("invalid number '{}'", token)))
+}
+
+/// Check that `text` is a rectangular grid of characters accepted by `is_tile`,
+/// and return its width and height.
+pub fn check_grid(
+    text: &str,
+    is_tile: impl Fn(char) -> bool,
+) -> Result<(usize, usize), ParseError> {
+    let grid = Grid::parse(text, |c| is_tile(c).then_some(()))?;
+    Ok((grid.width(), grid.height()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_position_of_token() {
+        let input = "R 6 (#70c710)\nD 5 (#0dc571)\nX 2 (#5713f0)";
+        let token = input.lines().nth(2).unwrap().split(' ').next().unwrap();
+        assert_eq!(
+            ParseError::at(input, token, "oops"),
+            ParseError::new(3, 1, "oops")
+        );
+
+        let line = input.lines().nth(1).unwrap();
+        let token = &line[5..];
+        assert_eq!(
+            ParseError::at(line, token, "oops").below(1),
+            ParseError::new(2, 6, "oops")
+        );
+        assert_eq!(ParseError::at(line, &line[line.len()..], "").column, 14);
+    }
+
+    #[test]
+    fn test_parse_number() {
+        let line = "Time: 7 1x5";
+        let mut tokens = line.split_ascii_whitespace().skip(1);
+        assert_eq!(parse_number::<u32>(line, tokens.next().unwrap()), Ok(7));
+        let err = parse_number::<u32>(line, tokens.next().unwrap()).unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 9: invalid number '1x5'");
+    }
+
+    #[test]
+    fn test_check_grid() {
+        let is_tile = |c| c == '.' || c == '#';
+        assert_eq!(check_grid("#..\n..#\n", is_tile), Ok((3, 2)));
+        assert_eq!(check_grid("#..\n.#\n", is_tile).unwrap_err().line, 2);
+        assert_eq!(check_grid("#..\n.x.\n", is_tile).unwrap_err().column, 2);
+        assert!(check_grid("", is_tile).is_err());
+    }
+
+    #[test]
+    fn test_report() {
+        let input = "broadcaster -> a\n%a -> b\n?b -> a";
+        let line = input.lines().nth(2).unwrap();
+        let err = ParseError::at(input, &line[..1], "unknown module type '?'");
+        assert_eq!(
+            err.report(input),
+            "line 3, column 1: unknown module type '?'\n  3 | ?b -> a\n    | ^"
+        );
+    }
+}
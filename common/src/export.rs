@@ -0,0 +1,342 @@
+//! Export of the puzzle states to image files, for reports.
+//!
+//! Grids of tiles are drawn as raster images, one `Rgb` pixel per tile, and saved as
+//! PPM or PNG, or as SVG with a square per pixel. Drawings made of shapes, such as
+//! polygons with coordinates too large for a grid, are built as `Svg` documents and
+//! can only be saved as SVG. `Picture::save` picks the format from the file
+//! extension.
+//!
+//! The PNG writer does not compress the pixels, which keeps it short and free of
+//! dependencies: the files are about as large as the PPM ones.
+
+use std::fmt::{self, Write as _};
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::grid::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb::new(0, 0, 0);
+    pub const WHITE: Rgb = Rgb::new(255, 255, 255);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Rgb { r, g, b }
+    }
+
+    /// Parse a color written like `#70c710`.
+    pub fn parse_hex(hex: &str) -> Option<Rgb> {
+        let digits = hex.strip_prefix('#')?;
+        if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).unwrap();
+        Some(Rgb::new(channel(0), channel(2), channel(4)))
+    }
+
+    /// Color between this one and `other`, at `t` from 0 (this one) to 1 (`other`).
+    pub fn mix(self, other: Rgb, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Rgb::new(
+            channel(self.r, other.r),
+            channel(self.g, other.g),
+            channel(self.b, other.b),
+        )
+    }
+}
+
+/// Written like `#70c710`, as in SVG.
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// The image with each pixel enlarged to a square of `factor` by `factor` pixels.
+pub fn scale(image: &Grid<Rgb>, factor: usize) -> Grid<Rgb> {
+    Grid::from_fn(image.width() * factor, image.height() * factor, |(x, y)| {
+        image[(x / factor, y / factor)]
+    })
+}
+
+/// Write the image as a binary PPM (P6) file.
+pub fn write_ppm(image: &Grid<Rgb>, out: &mut impl Write) -> io::Result<()> {
+    write!(out, "P6\n{} {}\n255\n", image.width(), image.height())?;
+    let pixels: Vec<u8> = image.iter().flat_map(|(_, p)| [p.r, p.g, p.b]).collect();
+    out.write_all(&pixels)
+}
+
+/// CRC-32 used by the PNG chunks, computed bit by bit.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0_u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Checksum ending the zlib stream of a PNG.
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for &byte in bytes {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+fn write_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    let mut chunk = kind.to_vec();
+    chunk.extend_from_slice(data);
+    out.write_all(&chunk)?;
+    out.write_all(&crc32(&chunk).to_be_bytes())
+}
+
+/// Write the image as an 8-bit RGB PNG file, stored without compression.
+pub fn write_png(image: &Grid<Rgb>, out: &mut impl Write) -> io::Result<()> {
+    out.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+    let mut header = vec![];
+    header.extend_from_slice(&(image.width() as u32).to_be_bytes());
+    header.extend_from_slice(&(image.height() as u32).to_be_bytes());
+    // Bit depth 8, true color, default compression, filtering and no interlacing
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+    write_chunk(out, b"IHDR", &header)?;
+
+    // Each row starts with its filter type, none
+    let mut pixels = vec![];
+    for row in image.rows() {
+        pixels.push(0);
+        pixels.extend(row.iter().flat_map(|p| [p.r, p.g, p.b]));
+    }
+    // zlib stream of stored deflate blocks, which hold up to 65535 bytes each
+    let mut data = vec![0x78, 0x01];
+    let mut blocks = pixels.chunks(65535).peekable();
+    if blocks.peek().is_none() {
+        data.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        data.push(last as u8);
+        data.extend_from_slice(&len.to_le_bytes());
+        data.extend_from_slice(&(!len).to_le_bytes());
+        data.extend_from_slice(block);
+    }
+    data.extend_from_slice(&adler32(&pixels).to_be_bytes());
+    write_chunk(out, b"IDAT", &data)?;
+
+    write_chunk(out, b"IEND", &[])
+}
+
+/// An SVG document, built shape by shape.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Svg {
+    /// Top left corner and size of the area drawn, in the coordinates of the shapes.
+    view: (f64, f64, f64, f64),
+    /// Size of a unit of the coordinates on screen.
+    scale: f64,
+    shapes: String,
+}
+
+impl Svg {
+    /// A document showing the area of `width` by `height` starting at (`x`, `y`).
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Svg {
+            view: (x, y, width, height),
+            scale: 1.0,
+            shapes: String::new(),
+        }
+    }
+
+    /// Show the document `scale` times larger than its coordinates.
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// The image drawn with a square of `size` by `size` per pixel, merging the
+    /// pixels of the same color next to each other on a row.
+    pub fn from_raster(image: &Grid<Rgb>, size: f64) -> Self {
+        let (width, height) = (image.width() as f64, image.height() as f64);
+        let mut svg = Svg::new(0.0, 0.0, width * size, height * size);
+        for (y, row) in image.rows().enumerate() {
+            let mut x = 0;
+            for run in row.chunk_by(|a, b| a == b) {
+                let (left, top) = (x as f64 * size, y as f64 * size);
+                svg.rect(left, top, run.len() as f64 * size, size, run[0]);
+                x += run.len();
+            }
+        }
+        svg
+    }
+
+    pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64, fill: Rgb) {
+        writeln!(
+            self.shapes,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            x, y, width, height, fill
+        )
+        .unwrap();
+    }
+
+    pub fn line(&mut self, from: (f64, f64), to: (f64, f64), stroke: Rgb, width: f64) {
+        writeln!(
+            self.shapes,
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}" stroke-linecap="square"/>"#,
+            from.0, from.1, to.0, to.1, stroke, width
+        )
+        .unwrap();
+    }
+
+    pub fn polygon(&mut self, points: &[(f64, f64)], fill: Rgb) {
+        let points: Vec<_> = points.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+        writeln!(
+            self.shapes,
+            r#"<polygon points="{}" fill="{}"/>"#,
+            points.join(" "),
+            fill
+        )
+        .unwrap();
+    }
+}
+
+impl fmt::Display for Svg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (x, y, width, height) = self.view;
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}" width="{}" height="{}">"#,
+            x,
+            y,
+            width,
+            height,
+            width * self.scale,
+            height * self.scale
+        )?;
+        f.write_str(&self.shapes)?;
+        writeln!(f, "</svg>")
+    }
+}
+
+/// An image of a puzzle state, ready to be saved.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Picture {
+    /// A grid of pixels, enlarged `scale` times when saved.
+    Raster {
+        image: Grid<Rgb>,
+        scale: usize,
+    },
+    Vector(Svg),
+}
+
+impl Picture {
+    /// Save the picture in the format given by the extension of `path`: `svg` for
+    /// any picture, `ppm` or `png` for a raster one.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        let mut out = io::BufWriter::new(match (self, extension) {
+            (_, "svg") | (Picture::Raster { .. }, "ppm" | "png") => std::fs::File::create(path)?,
+            (Picture::Vector(_), "ppm" | "png") => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "this picture is a vector drawing, which can only be saved as .svg",
+                ))
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "unknown image format, expected a .svg, .ppm or .png file",
+                ))
+            }
+        });
+        match self {
+            Picture::Vector(svg) => write!(out, "{}", svg)?,
+            Picture::Raster { image, scale } => match extension {
+                "svg" => write!(out, "{}", Svg::from_raster(image, *scale as f64))?,
+                "ppm" => write_ppm(&self::scale(image, *scale), &mut out)?,
+                _ => write_png(&self::scale(image, *scale), &mut out)?,
+            },
+        }
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn image() -> Grid<Rgb> {
+        let red = Rgb::new(255, 0, 0);
+        Grid::from_fn(3, 2, |(x, y)| if x == y { red } else { Rgb::WHITE })
+    }
+
+    #[test]
+    fn test_rgb() {
+        assert_eq!(Rgb::parse_hex("#70c710"), Some(Rgb::new(0x70, 0xc7, 0x10)));
+        assert_eq!(Rgb::parse_hex("70c710"), None);
+        assert_eq!(Rgb::parse_hex("#70c71"), None);
+        assert_eq!(Rgb::parse_hex("#70c71g"), None);
+        assert_eq!(Rgb::new(0x70, 0xc7, 0x10).to_string(), "#70c710");
+        assert_eq!(Rgb::BLACK.mix(Rgb::WHITE, 0.5), Rgb::new(128, 128, 128));
+        assert_eq!(Rgb::BLACK.mix(Rgb::WHITE, 2.0), Rgb::WHITE);
+    }
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn test_raster() {
+        let scaled = scale(&image(), 2);
+        assert_eq!((scaled.width(), scaled.height()), (6, 4));
+        assert_eq!(scaled[(3, 3)], Rgb::new(255, 0, 0));
+
+        let mut ppm = vec![];
+        write_ppm(&image(), &mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n3 2\n255\n\xff\x00\x00\xff\xff\xff"));
+        assert_eq!(ppm.len(), 11 + 3 * 2 * 3);
+
+        let mut png = vec![];
+        write_png(&image(), &mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR\x00\x00\x00\x03"));
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = Svg::from_raster(&image(), 10.0).to_string();
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 30 20""#));
+        // Runs of pixels of the same color are drawn as a single rectangle
+        assert!(svg.contains(r##"<rect x="10" y="0" width="20" height="10" fill="#ffffff"/>"##));
+        assert_eq!(svg.matches("<rect").count(), 5);
+        assert!(svg.ends_with("</svg>\n"));
+
+        let mut svg = Svg::new(-1.0, -1.0, 4.0, 4.0).with_scale(2.5);
+        svg.polygon(&[(0.0, 0.0), (2.0, 0.0), (2.0, 2.5)], Rgb::BLACK);
+        let svg = svg.to_string();
+        assert!(svg.contains(r#"viewBox="-1 -1 4 4" width="10" height="10""#));
+        assert!(svg.contains(r##"<polygon points="0,0 2,0 2,2.5" fill="#000000"/>"##));
+    }
+}
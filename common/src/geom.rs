@@ -0,0 +1,322 @@
+//! Points, vectors and directions on a plane, for the puzzles moving around a map.
+//!
+//! As on the maps of the puzzles, `x` grows to the right and `y` grows downwards, so
+//! `Direction::Up` decreases `y`.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/// Integer types usable as coordinates.
+pub trait Coordinate:
+    Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+
+    /// Absolute difference, which cannot overflow for the unsigned types.
+    fn abs_diff(self, other: Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+}
+
+macro_rules! impl_coordinate {
+    ($($t:ty),*) => {
+        $(impl Coordinate for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+        })*
+    };
+}
+
+impl_coordinate!(i32, i64, i128, isize, u32, u64, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All the directions, clockwise from `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Direction after a quarter turn clockwise.
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Direction after a quarter turn counterclockwise.
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+
+    pub fn is_vertical(self) -> bool {
+        !self.is_horizontal()
+    }
+
+    /// Vector of one step in this direction.
+    pub fn vector<T: Coordinate + Neg<Output = T>>(self) -> Vector<T> {
+        match self {
+            Direction::Up => Vector::new(T::ZERO, -T::ONE),
+            Direction::Right => Vector::new(T::ONE, T::ZERO),
+            Direction::Down => Vector::new(T::ZERO, T::ONE),
+            Direction::Left => Vector::new(-T::ONE, T::ZERO),
+        }
+    }
+
+    /// Arrow pointing in this direction, as drawn on the maps: `^`, `>`, `v` or `<`.
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// Difference between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Vector<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T: Coordinate> Point<T> {
+    /// The next point in `direction`, or `None` if a coordinate overflows, such as
+    /// when stepping up from `y = 0` with unsigned coordinates.
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        self.step_by(direction, T::ONE)
+    }
+
+    /// The point `distance` steps away in `direction`, or `None` if a coordinate
+    /// overflows.
+    pub fn step_by(self, direction: Direction, distance: T) -> Option<Self> {
+        let Point { x, y } = self;
+        Some(match direction {
+            Direction::Up => Point::new(x, y.checked_sub(distance)?),
+            Direction::Right => Point::new(x.checked_add(distance)?, y),
+            Direction::Down => Point::new(x, y.checked_add(distance)?),
+            Direction::Left => Point::new(x.checked_sub(distance)?, y),
+        })
+    }
+
+    /// The (up to) four points one step away, in the order of `Direction::ALL`.
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(direction))
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Direction to go from this point to `other`, if they are distinct points on
+    /// the same row or column.
+    pub fn direction_to(self, other: Self) -> Option<Direction> {
+        if self.x == other.x && self.y != other.y {
+            Some(if other.y < self.y {
+                Direction::Up
+            } else {
+                Direction::Down
+            })
+        } else if self.y == other.y && self.x != other.x {
+            Some(if other.x < self.x {
+                Direction::Left
+            } else {
+                Direction::Right
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Vector<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Vector { x, y }
+    }
+}
+
+impl<T: Coordinate> Vector<T> {
+    pub fn manhattan_length(self) -> T {
+        self.x.abs_diff(T::ZERO) + self.y.abs_diff(T::ZERO)
+    }
+}
+
+impl<T: Add<Output = T>> Add<Vector<T>> for Point<T> {
+    type Output = Point<T>;
+
+    fn add(self, rhs: Vector<T>) -> Point<T> {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Add<Output = T> + Copy> AddAssign<Vector<T>> for Point<T> {
+    fn add_assign(&mut self, rhs: Vector<T>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub<Vector<T>> for Point<T> {
+    type Output = Point<T>;
+
+    fn sub(self, rhs: Vector<T>) -> Point<T> {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Vector<T>;
+
+    fn sub(self, rhs: Point<T>) -> Vector<T> {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vector<T> {
+    type Output = Vector<T>;
+
+    fn add(self, rhs: Vector<T>) -> Vector<T> {
+        Vector::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vector<T> {
+    type Output = Vector<T>;
+
+    fn neg(self) -> Vector<T> {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vector<T> {
+    type Output = Vector<T>;
+
+    fn mul(self, rhs: T) -> Vector<T> {
+        Vector::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        use Direction::*;
+        assert_eq!(Up.turn_right(), Right);
+        assert_eq!(Up.turn_left(), Left);
+        assert_eq!(Left.turn_right(), Up);
+        assert_eq!(Down.reverse(), Up);
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.reverse());
+            assert_eq!(
+                direction.reverse().is_horizontal(),
+                direction.is_horizontal()
+            );
+        }
+    }
+
+    #[test]
+    fn test_step() {
+        let origin = Point::new(0_usize, 0);
+        assert_eq!(origin.step(Direction::Up), None);
+        assert_eq!(origin.step(Direction::Right), Some(Point::new(1, 0)));
+        assert_eq!(origin.step_by(Direction::Down, 5), Some(Point::new(0, 5)));
+        assert_eq!(origin.neighbours4().count(), 2);
+        assert_eq!(
+            Point::new(0_i32, 0).step(Direction::Up),
+            Some(Point::new(0, -1))
+        );
+        assert_eq!(Point::new(i32::MIN, 0).step(Direction::Left), None);
+    }
+
+    #[test]
+    fn test_vectors() {
+        let point = Point::new(3_i64, -2);
+        assert_eq!(point + Direction::Left.vector() * 4, Point::new(-1, -2));
+        assert_eq!(point - Point::new(1, 1), Vector::new(2, -3));
+        assert_eq!(-Direction::Up.vector::<i64>(), Direction::Down.vector());
+        let mut moved = point;
+        moved += Vector::new(1, 1);
+        assert_eq!(moved, Point::new(4, -1));
+        assert_eq!((moved - point).manhattan_length(), 2);
+    }
+
+    #[test]
+    fn test_distances() {
+        assert_eq!(
+            Point::new(1_usize, 6).manhattan_distance(Point::new(5, 11)),
+            9
+        );
+        assert_eq!(
+            Point::new(-3_i32, 2).manhattan_distance(Point::new(2, -1)),
+            8
+        );
+        let point = Point::new(2_usize, 2);
+        assert_eq!(point.direction_to(Point::new(2, 0)), Some(Direction::Up));
+        assert_eq!(point.direction_to(Point::new(3, 2)), Some(Direction::Right));
+        assert_eq!(point.direction_to(Point::new(3, 3)), None);
+        assert_eq!(point.direction_to(point), None);
+    }
+}
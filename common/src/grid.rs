@@ -0,0 +1,386 @@
+//! Rectangular grid of tiles, as found in most of the map puzzles.
+//!
+//! Positions are `(x, y)` pairs, where `x` is the column and `y` the row, both
+//! starting from 0 at the top left corner.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::geom::{Direction, Point};
+use crate::ParseError;
+
+/// Position of a tile, as `(x, y)`.
+pub type Position = (usize, usize);
+
+/// Offsets of the eight neighbours of a tile, clockwise from the top left one.
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// A grid of `width` by `height` tiles, stored row after row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Grid of `width` by `height` copies of `tile`.
+    pub fn new(width: usize, height: usize, tile: T) -> Self {
+        Grid {
+            data: vec![tile; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// The grid flipped over its main diagonal, so that rows become columns.
+    pub fn transpose(&self) -> Self {
+        let data = (0..self.width)
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Grid {
+            data,
+            width: self.height,
+            height: self.width,
+        }
+    }
+
+    /// The grid rotated a quarter turn clockwise: the first row becomes the last
+    /// column.
+    pub fn rotate_clockwise(&self) -> Self {
+        let data = (0..self.width)
+            .flat_map(|x| self.column(x).rev().cloned())
+            .collect();
+        Grid {
+            data,
+            width: self.height,
+            height: self.width,
+        }
+    }
+
+    /// The grid rotated a quarter turn counterclockwise: the first row becomes the
+    /// first column, upside down.
+    pub fn rotate_counterclockwise(&self) -> Self {
+        let data = (0..self.width)
+            .rev()
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Grid {
+            data,
+            width: self.height,
+            height: self.width,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parse a rectangular grid of characters, one row per line, converting each of
+    /// them with `tile`. Characters for which it returns `None` are reported as
+    /// unknown tiles.
+    pub fn parse(text: &str, tile: impl Fn(char) -> Option<T>) -> Result<Self, ParseError> {
+        let width = text.lines().next().map_or(0, |line| line.chars().count());
+        if width == 0 {
+            return Err(ParseError::new(1, 1, "expected a grid"));
+        }
+        let mut data = vec![];
+        let mut height = 0;
+        for (y, line) in text.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let tile = tile(c).ok_or_else(|| {
+                    ParseError::new(y + 1, x + 1, format!("unknown tile '{}'", c))
+                })?;
+                data.push(tile);
+            }
+            if line.chars().count() != width {
+                return Err(ParseError::new(
+                    y + 1,
+                    1,
+                    format!("expected a row of {} tiles like the first one", width),
+                ));
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            data,
+            width,
+            height,
+        })
+    }
+
+    /// Grid of `width` by `height` tiles, where the tile at each position is given by
+    /// `tile`.
+    pub fn from_fn(width: usize, height: usize, mut tile: impl FnMut(Position) -> T) -> Self {
+        let data = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut tile)
+            .collect();
+        Grid {
+            data,
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Position) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position).then(|| &self[position])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            Some(&mut self[position])
+        } else {
+            None
+        }
+    }
+
+    /// Position at signed coordinates, if it is on the grid.
+    pub fn checked_position(&self, x: isize, y: isize) -> Option<Position> {
+        let position = (usize::try_from(x).ok()?, usize::try_from(y).ok()?);
+        self.contains(position).then_some(position)
+    }
+
+    /// Tile at signed coordinates, or `None` off the grid.
+    pub fn get_signed(&self, x: isize, y: isize) -> Option<&T> {
+        self.checked_position(x, y).map(|position| &self[position])
+    }
+
+    /// Position on the grid of any signed coordinates, for a grid repeated infinitely
+    /// in every direction.
+    pub fn wrap(&self, x: isize, y: isize) -> Position {
+        (
+            x.rem_euclid(self.width as isize) as usize,
+            y.rem_euclid(self.height as isize) as usize,
+        )
+    }
+
+    /// Tile at signed coordinates, for a grid repeated infinitely in every direction.
+    pub fn get_wrapped(&self, x: isize, y: isize) -> &T {
+        &self[self.wrap(x, y)]
+    }
+
+    /// Position one step away from `position` in `direction`, if it is on the grid.
+    pub fn step(&self, position: Position, direction: Direction) -> Option<Position> {
+        let next = Point::from(position).step(direction)?.into();
+        self.contains(next).then_some(next)
+    }
+
+    /// Positions of the (up to) four tiles sharing a side with the one at `position`:
+    /// up, right, down and left.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction))
+    }
+
+    /// Positions of the (up to) eight tiles around the one at `position`, diagonals
+    /// included.
+    pub fn neighbours8(&self, (x, y): Position) -> impl Iterator<Item = Position> + '_ {
+        OFFSETS_8
+            .iter()
+            .filter_map(move |&(dx, dy)| self.checked_position(x as isize + dx, y as isize + dy))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.data[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        self.data.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.data[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// All the positions, row after row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All the tiles with their position, row after row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(&self.data)
+    }
+
+    /// Position of the first tile, in reading order, matching `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Position> {
+        let index = self.data.iter().position(predicate)?;
+        Some((index % self.width, index / self.width))
+    }
+
+    /// Grid of the same size, with each tile converted by `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): Position) -> &T {
+        assert!(
+            x < self.width && y < self.height,
+            "position ({}, {}) is out of a {}x{} grid",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        &self.data[x + y * self.width]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, (x, y): Position) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "position ({}, {}) is out of a {}x{} grid",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        &mut self.data[x + y * self.width]
+    }
+}
+
+/// Draw the grid one row per line, each tile formatted with its own `Display`.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for tile in row {
+                write!(f, "{}", tile)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n", Some).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 'f');
+        let digits = Grid::parse("12\n34", |c| c.to_digit(10)).unwrap();
+        assert_eq!(digits.row(1), &[3, 4]);
+        assert_eq!(
+            Grid::parse("12\n3x", |c| c.to_digit(10)),
+            Err(ParseError::new(2, 2, "unknown tile 'x'"))
+        );
+        assert_eq!(Grid::parse("12\n3", Some).unwrap_err().line, 2);
+        assert!(Grid::parse("", Some).is_err());
+    }
+
+    #[test]
+    fn test_access() {
+        let mut grid = grid();
+        assert_eq!(grid.get((1, 1)), Some(&'e'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get_signed(-1, 0), None);
+        assert_eq!(grid.get_signed(0, 1), Some(&'d'));
+        assert_eq!(grid.get_wrapped(-1, 2), &'c');
+        assert_eq!(grid.wrap(7, -3), (1, 1));
+        grid[(0, 0)] = 'z';
+        *grid.get_mut((1, 0)).unwrap() = 'y';
+        assert_eq!(grid.row(0), &['z', 'y', 'c']);
+        assert_eq!(grid.position(|&c| c == 'e'), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(
+            grid.neighbours4((1, 1)).collect::<Vec<_>>(),
+            [(1, 0), (2, 1), (1, 2), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.step((2, 1), Direction::Right), None);
+        assert_eq!(grid.step((2, 1), Direction::Left), Some((1, 1)));
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbours8((2, 2)).collect::<Vec<_>>(),
+            [(1, 1), (2, 1), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = grid();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [['a', 'b', 'c'], ['d', 'e', 'f']]
+        );
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.columns().map(|c| c.collect()).collect::<Vec<String>>(),
+            ["ad", "be", "cf"]
+        );
+        assert_eq!(grid.iter().nth(4), Some(((1, 1), &'e')));
+    }
+
+    #[test]
+    fn test_rotate_and_transpose() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad\n");
+        let mut rotated = grid.clone();
+        for _ in 0..4 {
+            rotated = rotated.rotate_clockwise();
+        }
+        assert_eq!(rotated, grid);
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+
+    #[test]
+    fn test_from_fn_and_map() {
+        let grid = Grid::from_fn(3, 2, |(x, y)| x + 10 * y);
+        assert_eq!(grid.row(1), &[10, 11, 12]);
+        assert_eq!(
+            grid.map(|n| if n % 3 == 0 { '#' } else { '.' }).to_string(),
+            "#..\n..#\n"
+        );
+    }
+}
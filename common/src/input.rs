@@ -0,0 +1,131 @@
+use std::fmt;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+/// Environment variable consulted for the puzzle input when none is given on the
+/// command line. It accepts the same values as the command line argument.
+pub const INPUT_ENV_VAR: &str = "AOC_INPUT";
+
+/// Where to read the puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// Interpret an input argument for the given day.
+    ///
+    /// `-` means stdin. Anything else is a path, in which `{day}` is replaced by
+    /// the day number so that a single pattern such as `inputs/alice/{day}.txt`
+    /// can point at the inputs of every day.
+    pub fn from_arg(arg: &str, day: u8) -> Self {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg.replace("{day}", &day.to_string())))
+        }
+    }
+
+    /// Pick the input source for a day: the command line argument if there is one,
+    /// then the `AOC_INPUT` environment variable, then `default`.
+    pub fn resolve(arg: Option<&str>, default: impl Into<PathBuf>, day: u8) -> Self {
+        match arg {
+            Some(arg) => InputSource::from_arg(arg, day),
+            None => match std::env::var(INPUT_ENV_VAR) {
+                Ok(var) if !var.is_empty() => InputSource::from_arg(&var, day),
+                _ => InputSource::File(default.into()),
+            },
+        }
+    }
+
+    pub fn read(&self) -> Result<String, InputError> {
+        let result = match self {
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input).map(|_| input)
+            }
+            InputSource::File(path) => std::fs::read_to_string(path),
+        };
+        result.map_err(|error| InputError {
+            source: self.clone(),
+            error,
+        })
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// The puzzle input could not be read.
+#[derive(Debug)]
+pub struct InputError {
+    pub source: InputSource,
+    pub error: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "could not read puzzle input from {}: {}",
+            self.source, self.error
+        )?;
+        if self.error.kind() == io::ErrorKind::NotFound {
+            write!(
+                f,
+                " (give another input path, '-' for stdin, or set {})",
+                INPUT_ENV_VAR
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Read the input of a day's standalone binary: from the first command line
+/// argument, `AOC_INPUT` or `input.txt` in the current directory.
+/// Prints the error and exits if the input cannot be read.
+pub fn read_main_input(day: u8) -> String {
+    let arg = std::env::args().nth(1);
+    match InputSource::resolve(arg.as_deref(), "input.txt", day).read() {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(InputSource::from_arg("-", 3), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("inputs/alice/{day}.txt", 17),
+            InputSource::File(PathBuf::from("inputs/alice/17.txt"))
+        );
+        assert_eq!(
+            InputSource::from_arg("example.txt", 5),
+            InputSource::File(PathBuf::from("example.txt"))
+        );
+    }
+
+    #[test]
+    fn test_missing_file_error() {
+        let source = InputSource::File(PathBuf::from("does/not/exist.txt"));
+        let message = source.read().unwrap_err().to_string();
+        assert!(message.starts_with("could not read puzzle input from does/not/exist.txt"));
+        assert!(message.contains(INPUT_ENV_VAR));
+    }
+}
@@ -0,0 +1,374 @@
+//! Half-open intervals of integers, sets of them, and N-dimensional boxes, for the
+//! puzzles working on ranges of values too large to go through one by one.
+//!
+//! An `Interval` holds the values from `start` included to `end` excluded, so that
+//! intervals touching end to start cover a range without overlapping, and an interval
+//! of length `n` starting at `s` is simply `Interval::new(s, s + n)`.
+
+use std::fmt;
+use std::ops::Range;
+
+use crate::geom::Coordinate;
+
+/// The values from `start` included to `end` excluded. Empty when `start >= end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T> Interval<T> {
+    pub const fn new(start: T, end: T) -> Self {
+        Interval { start, end }
+    }
+}
+
+impl<T: Coordinate> Interval<T> {
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// Number of values in the interval.
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::ZERO
+        } else {
+            self.end - self.start
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// Values in both intervals. The result is empty if they do not overlap.
+    pub fn intersection(&self, other: &Interval<T>) -> Interval<T> {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// The interval moved by `delta`, which is negative to move it down.
+    pub fn shift(&self, delta: T) -> Interval<T> {
+        Interval::new(self.start + delta, self.end + delta)
+    }
+}
+
+impl<T> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Interval::new(range.start, range.end)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+/// A set of values, stored as sorted intervals that neither overlap nor touch.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { intervals: vec![] }
+    }
+}
+
+impl<T: Coordinate> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The intervals of the set, sorted.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of values in the set.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::ZERO, |total, interval| total + interval.len())
+    }
+
+    /// Smallest value in the set.
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self
+            .intervals
+            .partition_point(|interval| interval.end <= value);
+        self.intervals
+            .get(idx)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    /// Add the values of `interval`, merging it with the intervals it overlaps or
+    /// touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        let first = self
+            .intervals
+            .partition_point(|other| other.end < interval.start);
+        let last = self
+            .intervals
+            .partition_point(|other| other.start <= interval.end);
+        let mut merged = interval;
+        if first < last {
+            merged.start = merged.start.min(self.intervals[first].start);
+            merged.end = merged.end.max(self.intervals[last - 1].end);
+        }
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut union = self.clone();
+        for &interval in &other.intervals {
+            union.insert(interval);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = vec![];
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            let common = a.intersection(b);
+            if !common.is_empty() {
+                intervals.push(common);
+            }
+            // Move past the interval ending first, which cannot overlap anything else
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    /// Values of this set which are not in `other`.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = vec![];
+        let mut removed = other.intervals.iter().peekable();
+        for &interval in &self.intervals {
+            let mut rest = interval;
+            while let Some(hole) = removed.peek() {
+                if hole.end <= rest.start {
+                    removed.next();
+                    continue;
+                }
+                if hole.start >= rest.end {
+                    break;
+                }
+                if hole.start > rest.start {
+                    intervals.push(Interval::new(rest.start, hole.start));
+                }
+                rest.start = hole.end;
+                if rest.is_empty() {
+                    break;
+                }
+                removed.next();
+            }
+            if !rest.is_empty() {
+                intervals.push(rest);
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    /// The set with all its values moved by `delta`.
+    pub fn shift(&self, delta: T) -> IntervalSet<T> {
+        IntervalSet {
+            intervals: self.intervals.iter().map(|i| i.shift(delta)).collect(),
+        }
+    }
+
+    /// Cut the intervals of the set at each of the `breakpoints`, which then only
+    /// appear at the start of a piece. The pieces are sorted, and each of them lies
+    /// entirely on one side of every breakpoint.
+    pub fn split(&self, breakpoints: impl IntoIterator<Item = T>) -> Vec<Interval<T>> {
+        let mut breakpoints: Vec<T> = breakpoints.into_iter().collect();
+        breakpoints.sort_unstable();
+        breakpoints.dedup();
+        let mut pieces = vec![];
+        for interval in &self.intervals {
+            let mut start = interval.start;
+            let first = breakpoints.partition_point(|&point| point <= start);
+            for &point in breakpoints[first..].iter() {
+                if point >= interval.end {
+                    break;
+                }
+                pieces.push(Interval::new(start, point));
+                start = point;
+            }
+            pieces.push(Interval::new(start, interval.end));
+        }
+        pieces
+    }
+}
+
+impl<T: Coordinate> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(interval);
+        set
+    }
+}
+
+impl<T: Coordinate> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+/// An N-dimensional box: the points whose coordinate on each axis lies in the
+/// interval of that axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid<T, const N: usize> {
+    pub axes: [Interval<T>; N],
+}
+
+impl<T: Coordinate, const N: usize> Cuboid<T, N> {
+    pub const fn new(axes: [Interval<T>; N]) -> Self {
+        Cuboid { axes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.axes.iter().any(Interval::is_empty)
+    }
+
+    /// Number of points in the box.
+    pub fn volume(&self) -> T {
+        if self.is_empty() {
+            return T::ZERO;
+        }
+        self.axes
+            .iter()
+            .fold(T::ONE, |volume, interval| volume * interval.len())
+    }
+
+    pub fn contains(&self, point: [T; N]) -> bool {
+        self.axes
+            .iter()
+            .zip(point)
+            .all(|(interval, value)| interval.contains(value))
+    }
+
+    pub fn intersection(&self, other: &Cuboid<T, N>) -> Cuboid<T, N> {
+        let mut axes = self.axes;
+        for (axis, interval) in axes.iter_mut().zip(&other.axes) {
+            *axis = axis.intersection(interval);
+        }
+        Cuboid { axes }
+    }
+
+    /// The part of the box whose coordinate on `axis` lies in `interval`.
+    pub fn restrict(&self, axis: usize, interval: Interval<T>) -> Cuboid<T, N> {
+        let mut axes = self.axes;
+        axes[axis] = axes[axis].intersection(&interval);
+        Cuboid { axes }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet<i64> {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let interval = Interval::from(3..7_i64);
+        assert_eq!(interval.len(), 4);
+        assert!(interval.contains(3));
+        assert!(!interval.contains(7));
+        assert_eq!(interval.shift(-5), Interval::new(-2, 2));
+        assert_eq!(
+            interval.intersection(&Interval::new(5, 10)),
+            Interval::new(5, 7)
+        );
+        assert!(!interval.overlaps(&Interval::new(7, 10)));
+        assert_eq!(Interval::new(7, 3).len(), 0);
+        assert_eq!(interval.to_string(), "[3, 7)");
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut set = set(&[(0, 2), (5, 7), (10, 12)]);
+        assert_eq!(set.len(), 6);
+        // Touching intervals are merged
+        set.insert(Interval::new(2, 3));
+        assert_eq!(set, self::set(&[(0, 3), (5, 7), (10, 12)]));
+        set.insert(Interval::new(6, 10));
+        assert_eq!(set, self::set(&[(0, 3), (5, 12)]));
+        set.insert(Interval::new(-5, -4));
+        set.insert(Interval::new(20, 20));
+        assert_eq!(set.intervals().len(), 3);
+        assert_eq!(set.min(), Some(-5));
+        assert!(set.contains(0) && set.contains(11));
+        assert!(!set.contains(3) && !set.contains(12) && !set.contains(-6));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25), (28, 40)]);
+        assert_eq!(a.union(&b), set(&[(0, 40)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25), (28, 30)]));
+        assert_eq!(a.difference(&b), set(&[(0, 5), (25, 28)]));
+        assert_eq!(b.difference(&a), set(&[(10, 20), (30, 40)]));
+        assert_eq!(
+            a.difference(&set(&[(2, 3), (4, 6)])),
+            set(&[(0, 2), (3, 4), (6, 10), (20, 30)])
+        );
+        assert!(a.difference(&a).is_empty());
+        assert_eq!(a.shift(5), set(&[(5, 15), (25, 35)]));
+    }
+
+    #[test]
+    fn test_split() {
+        let set = set(&[(0, 10), (20, 30)]);
+        assert_eq!(
+            set.split([25, 5, 0, 15, 5]),
+            [(0, 5), (5, 10), (20, 25), (25, 30)].map(|(s, e)| Interval::new(s, e))
+        );
+        assert_eq!(set.split([]), set.intervals());
+    }
+
+    #[test]
+    fn test_cuboid() {
+        let cuboid = Cuboid::new([Interval::new(0_u64, 4), Interval::new(0, 3)]);
+        assert_eq!(cuboid.volume(), 12);
+        assert!(cuboid.contains([3, 2]));
+        assert!(!cuboid.contains([4, 2]));
+        let other = Cuboid::new([Interval::new(2, 10), Interval::new(1, 10)]);
+        assert_eq!(cuboid.intersection(&other).volume(), 4);
+        assert_eq!(cuboid.restrict(1, Interval::new(2, 5)).volume(), 4);
+        assert!(cuboid.restrict(0, Interval::new(4, 5)).is_empty());
+        assert_eq!(cuboid.restrict(0, Interval::new(4, 5)).volume(), 0);
+    }
+}
@@ -0,0 +1,22 @@
+//! Code shared between the solvers of every day and the `aoc` runner.
+
+pub mod animation;
+mod answer;
+pub mod cycle;
+mod error;
+pub mod export;
+pub mod geom;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod log;
+pub mod math;
+pub mod memo;
+pub mod metrics;
+pub mod random;
+pub mod repl;
+pub mod search;
+
+pub use answer::{print_answer, Answer};
+pub use error::{check_grid, parse_number, ParseError};
+pub use grid::Grid;
@@ -0,0 +1,164 @@
+//! Leveled logging to stderr, for the trace points of the solvers.
+//!
+//! Messages are written with the `error!`, `warn!`, `info!`, `debug!` and `trace!`
+//! macros, which take the same arguments as `println!`. Messages above the current
+//! level are not even formatted, so trace points can stay in hot loops.
+//!
+//! The level is read from the `AOC_LOG` environment variable (`off`, `error`,
+//! `warn`, `info`, `debug` or `trace`), and defaults to `warn`. The `aoc` runner
+//! can also set it from its command line.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering::Relaxed};
+
+/// Environment variable holding the log level.
+pub const LOG_ENV_VAR: &str = "AOC_LOG";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+const LEVELS: [Level; 6] = [
+    Level::Off,
+    Level::Error,
+    Level::Warn,
+    Level::Info,
+    Level::Debug,
+    Level::Trace,
+];
+
+impl Level {
+    pub fn name(self) -> &'static str {
+        match self {
+            Level::Off => "off",
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LEVELS
+            .into_iter()
+            .find(|level| level.name().eq_ignore_ascii_case(s))
+            .ok_or(format!(
+                "invalid log level '{}', expected off, error, warn, info, debug or trace",
+                s
+            ))
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+const DEFAULT_LEVEL: Level = Level::Warn;
+/// Value of `LEVEL` before the level is set or read from the environment.
+const UNSET: u8 = u8::MAX;
+
+static LEVEL: AtomicU8 = AtomicU8::new(UNSET);
+
+/// Set the log level, overriding `AOC_LOG`.
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Relaxed);
+}
+
+/// The current log level.
+pub fn level() -> Level {
+    match LEVEL.load(Relaxed) {
+        UNSET => {
+            let level = std::env::var(LOG_ENV_VAR)
+                .ok()
+                .and_then(|var| var.parse().ok())
+                .unwrap_or(DEFAULT_LEVEL);
+            set_level(level);
+            level
+        }
+        level => LEVELS[level as usize],
+    }
+}
+
+/// Whether messages at `level` are written. Use it to skip building expensive
+/// messages, such as drawings of the puzzle state.
+pub fn enabled(level: Level) -> bool {
+    level != Level::Off && level <= self::level()
+}
+
+#[doc(hidden)]
+pub fn write(level: Level, target: &str, args: fmt::Arguments) {
+    eprintln!("{:<5} {}: {}", level.name().to_uppercase(), target, args);
+}
+
+/// Log a message at the given level, formatted like `println!`.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::log::enabled($level) {
+            $crate::log::write($level, module_path!(), format_args!($($arg)+));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Error, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Warn, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_level() {
+        assert_eq!("debug".parse(), Ok(Level::Debug));
+        assert_eq!("TRACE".parse(), Ok(Level::Trace));
+        assert_eq!("off".parse(), Ok(Level::Off));
+        assert!("verbose".parse::<Level>().is_err());
+    }
+
+    #[test]
+    fn test_enabled() {
+        set_level(Level::Info);
+        assert!(enabled(Level::Warn));
+        assert!(enabled(Level::Info));
+        assert!(!enabled(Level::Debug));
+        set_level(Level::Off);
+        assert!(!enabled(Level::Error));
+        assert!(!enabled(Level::Off));
+    }
+}
@@ -0,0 +1,139 @@
+//! Number theory helpers: greatest common divisor, least common multiple, extended
+//! Euclid and the Chinese remainder theorem.
+//!
+//! The functions returning an `Option` give `None` when the result does not fit in
+//! the integer type, instead of silently wrapping around.
+
+use std::ops::{Div, Rem};
+
+/// Unsigned integer types accepted by `gcd` and `lcm`.
+pub trait Unsigned: Copy + Eq + Ord + Div<Output = Self> + Rem<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {
+        $(impl Unsigned for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+        })*
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+
+/// Greatest common divisor. `gcd(0, 0)` is 0.
+pub fn gcd<T: Unsigned>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, or `None` if it overflows. `lcm(0, n)` is 0.
+pub fn lcm<T: Unsigned>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Least common multiple of all the `numbers`, or `None` if it overflows.
+/// The least common multiple of no numbers is 1.
+pub fn lcm_all<T: Unsigned>(numbers: impl IntoIterator<Item = T>) -> Option<T> {
+    numbers.into_iter().try_fold(T::ONE, |acc, n| lcm(acc, n))
+}
+
+/// Extended Euclidean algorithm: returns `(g, x, y)` such that `a * x + b * y = g`,
+/// where `g` is the (non-negative) greatest common divisor of `a` and `b`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Chinese remainder theorem: find `x` such that `x ≡ residue (mod modulus)` for
+/// every `(residue, modulus)` congruence. The moduli must be positive, but do not
+/// need to be coprime.
+///
+/// Returns `(x, m)` where `m` is the least common multiple of the moduli and
+/// `0 <= x < m`, so that the solutions are exactly the `x + k * m`. Returns `None`
+/// if the congruences are incompatible, or if `m` does not fit in an `i128`.
+pub fn crt(congruences: impl IntoIterator<Item = (i128, i128)>) -> Option<(i128, i128)> {
+    let mut solution = (0, 1);
+    for (residue, modulus) in congruences {
+        assert!(modulus > 0, "modulus {} is not positive", modulus);
+        let (x, m) = solution;
+        let residue = residue.rem_euclid(modulus);
+        // Solve x + m * k ≡ residue (mod modulus) for k
+        let (g, inverse, _) = extended_gcd(m, modulus);
+        let diff = residue - x;
+        if diff % g != 0 {
+            return None;
+        }
+        let step = modulus / g;
+        let k = (diff / g)
+            .rem_euclid(step)
+            .checked_mul(inverse.rem_euclid(step))?
+            % step;
+        let new_m = m.checked_mul(step)?;
+        solution = (m.checked_mul(k)?.checked_add(x)?.rem_euclid(new_m), new_m);
+    }
+    Some(solution)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12_u64, 18), 6);
+        assert_eq!(gcd(0_u64, 7), 7);
+        assert_eq!(lcm(4_u64, 6), Some(12));
+        assert_eq!(lcm(0_u64, 6), Some(0));
+        assert_eq!(lcm_all([2_u64, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all::<u64>([]), Some(1));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(
+            lcm(u64::MAX as u128, (u64::MAX - 1) as u128),
+            Some(u64::MAX as u128 * (u64::MAX - 1) as u128)
+        );
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (46, 240), (-240, 46), (7, 0), (0, 7), (17, 5)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a.unsigned_abs(), b.unsigned_abs()) as i128);
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Moduli that are not coprime
+        assert_eq!(crt([(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(0, i128::MAX), (0, i128::MAX - 1)]), None);
+    }
+}
@@ -0,0 +1,220 @@
+//! Memoisation of recursive searches: a cache of the values already computed, which
+//! counts how often it is hit, to see whether it pays off.
+//!
+//! A recursive function takes the cache as `&mut Memo<K, V>`, returns the cached
+//! value if there is one, and caches its result otherwise:
+//!
+//! ```
+//! use aoc_common::memo::Memo;
+//!
+//! fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+//!     if n < 2 {
+//!         return n;
+//!     }
+//!     if let Some(&value) = memo.get(&n) {
+//!         return value;
+//!     }
+//!     let value = fibonacci(n - 1, memo) + fibonacci(n - 2, memo);
+//!     *memo.insert(n, value)
+//! }
+//!
+//! let mut memo = Memo::new();
+//! assert_eq!(fibonacci(50, &mut memo), 12586269025);
+//! assert_eq!(memo.len(), 49);
+//! ```
+//!
+//! The keys can borrow from the input, like the slices of a line, so that looking
+//! them up allocates nothing. Like with a `HashMap`, a key owning its data can be
+//! looked up by a borrowed form of it, a `String` by a `&str`.
+
+use std::borrow::Borrow;
+use std::cell::Cell;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::AddAssign;
+
+use crate::metrics;
+
+/// Values computed for each key, and how many lookups found them.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    values: HashMap<K, V>,
+    hits: Cell<usize>,
+    misses: Cell<usize>,
+}
+
+impl<K: Hash + Eq, V> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            values: HashMap::new(),
+            hits: Cell::new(0),
+            misses: Cell::new(0),
+        }
+    }
+
+    /// The value cached for `key`, counted as a hit, or a miss if there is none.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let value = self.values.get(key);
+        let counter = if value.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.set(counter.get() + 1);
+        value
+    }
+
+    /// Cache the value of `key`, replacing any previous one, and return it.
+    pub fn insert(&mut self, key: K, value: V) -> &V {
+        match self.values.entry(key) {
+            Entry::Occupied(mut entry) => {
+                entry.insert(value);
+                entry.into_mut()
+            }
+            Entry::Vacant(entry) => entry.insert(value),
+        }
+    }
+
+    /// The value cached for `key`, or the one `compute` gives, which is cached. For
+    /// functions which are not recursive: `compute` cannot use the cache.
+    pub fn get_or_insert_with(&mut self, key: K, compute: impl FnOnce(&K) -> V) -> &V {
+        match self.values.entry(key) {
+            Entry::Occupied(entry) => {
+                self.hits.set(self.hits.get() + 1);
+                entry.into_mut()
+            }
+            Entry::Vacant(entry) => {
+                self.misses.set(self.misses.get() + 1);
+                let value = compute(entry.key());
+                entry.insert(value)
+            }
+        }
+    }
+
+    /// Number of values cached.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            hits: self.hits.get(),
+            misses: self.misses.get(),
+            entries: self.values.len(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo::new()
+    }
+}
+
+/// How well a cache performed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stats {
+    /// Lookups which found a value.
+    pub hits: usize,
+    /// Lookups which found none, and had to compute it.
+    pub misses: usize,
+    /// Values cached.
+    pub entries: usize,
+}
+
+impl Stats {
+    /// Fraction of the lookups which found a value, from 0 to 1.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+
+    /// Add the stats to the metrics of the solver, as `memo_hits`, `memo_misses` and
+    /// `memo_entries`.
+    pub fn record(&self) {
+        metrics::add("memo_hits", self.hits as u64);
+        metrics::add("memo_misses", self.misses as u64);
+        metrics::add("memo_entries", self.entries as u64);
+    }
+}
+
+/// Add up the stats of several caches, like one for each line of the input.
+impl AddAssign for Stats {
+    fn add_assign(&mut self, other: Stats) {
+        self.hits += other.hits;
+        self.misses += other.misses;
+        self.entries += other.entries;
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate), {} entries",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0,
+            self.entries
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_memo() {
+        let mut memo: Memo<String, usize> = Memo::new();
+        assert_eq!(memo.get("abc"), None);
+        assert_eq!(*memo.insert("abc".to_string(), 3), 3);
+        assert_eq!(memo.get("abc"), Some(&3));
+        assert_eq!(*memo.get_or_insert_with("abc".to_string(), |_| 0), 3);
+        assert_eq!(
+            *memo.get_or_insert_with("de".to_string(), |key| key.len()),
+            2
+        );
+        assert_eq!(*memo.insert("abc".to_string(), 4), 4);
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 2,
+                misses: 2,
+                entries: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut stats = Stats {
+            hits: 3,
+            misses: 1,
+            entries: 1,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "3 hits, 1 misses (75.0% hit rate), 1 entries"
+        );
+        stats += Stats {
+            hits: 1,
+            misses: 5,
+            entries: 5,
+        };
+        assert_eq!(stats.hit_rate(), 0.4);
+        assert_eq!(Stats::default().hit_rate(), 0.0);
+    }
+}
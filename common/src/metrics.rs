@@ -0,0 +1,70 @@
+//! Counters of the work done by the solvers, like the states a search expanded, or
+//! the hits of a cache, to see why a solver is slow. The `aoc` runner prints them
+//! next to the answers.
+//!
+//! The counters belong to the thread which updates them, so that solvers running side
+//! by side do not mix theirs: a solver which spawns threads adds up their counts on
+//! its own thread. Updating a counter looks it up by name, so hot loops should count
+//! in a local variable, and add it once done.
+
+use std::cell::RefCell;
+
+/// Counters by name, in the order they were first updated.
+pub type Metrics = Vec<(&'static str, u64)>;
+
+thread_local! {
+    static METRICS: RefCell<Metrics> = const { RefCell::new(Vec::new()) };
+}
+
+fn update(name: &'static str, update: impl FnOnce(&mut u64)) {
+    METRICS.with_borrow_mut(|metrics| {
+        let index = match metrics.iter().position(|(other, _)| *other == name) {
+            Some(index) => index,
+            None => {
+                metrics.push((name, 0));
+                metrics.len() - 1
+            }
+        };
+        update(&mut metrics[index].1);
+    });
+}
+
+/// Add `n` to the counter `name`, which starts at 0.
+pub fn add(name: &'static str, n: u64) {
+    update(name, |value| *value += n);
+}
+
+/// Set the counter `name` to `value`, for measures which do not add up, like the
+/// length of a cycle.
+pub fn set(name: &'static str, value: u64) {
+    update(name, |old| *old = value);
+}
+
+/// The counters of the current thread, which start again from nothing.
+pub fn take() -> Metrics {
+    METRICS.with_borrow_mut(std::mem::take)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_metrics() {
+        take();
+        add("expanded", 3);
+        set("cycle_length", 7);
+        add("expanded", 2);
+        set("cycle_length", 4);
+        assert_eq!(take(), vec![("expanded", 5), ("cycle_length", 4)]);
+        assert_eq!(take(), vec![]);
+    }
+
+    #[test]
+    fn test_metrics_per_thread() {
+        take();
+        add("expanded", 1);
+        std::thread::spawn(|| add("expanded", 10)).join().unwrap();
+        assert_eq!(take(), vec![("expanded", 1)]);
+    }
+}
@@ -0,0 +1,237 @@
+//! Seeded pseudo-random numbers, for the generators of random puzzle inputs.
+//!
+//! The same seed always gives the same numbers, on every platform, so that a failing
+//! input can be generated again from its seed alone. The numbers come from SplitMix64,
+//! which is fast and good enough to shuffle puzzle inputs, but not for cryptography.
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use crate::grid::{Grid, Position};
+
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform number below `n`, which must not be 0.
+    fn below(&mut self, n: u64) -> u64 {
+        // Reject the numbers of the last, incomplete, stretch of `n` values
+        let limit = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < limit {
+                return x % n;
+            }
+        }
+    }
+
+    /// Uniform number in `range`, which must not be empty.
+    pub fn range<T: Uniform>(&mut self, range: RangeInclusive<T>) -> T {
+        T::sample(self, *range.start(), *range.end())
+    }
+
+    /// Uniform index into a slice of `len` elements, which must not be 0.
+    pub fn index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "no index to choose from");
+        self.below(len as u64) as usize
+    }
+
+    /// Uniform number from 0 included to 1 excluded.
+    pub fn float(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// `true` with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        self.float() < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    /// Put the items in a uniformly random order.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+/// Integer types which `Rng::range` can draw.
+pub trait Uniform: Copy + PartialOrd {
+    fn sample(rng: &mut Rng, low: Self, high: Self) -> Self;
+}
+
+macro_rules! impl_uniform {
+    ($($t:ty),*) => {
+        $(impl Uniform for $t {
+            fn sample(rng: &mut Rng, low: Self, high: Self) -> Self {
+                assert!(low <= high, "empty range {}..={}", low, high);
+                let span = (high as i128 - low as i128) as u64;
+                let offset = match span.checked_add(1) {
+                    Some(n) => rng.below(n),
+                    None => rng.next_u64(),
+                };
+                (low as i128 + offset as i128) as $t
+            }
+        })*
+    };
+}
+
+impl_uniform!(u8, u32, u64, usize, i32, i64, isize);
+
+/// Whether the blob can grow over `cell` and still have an outline made of a single
+/// loop which never touches itself.
+fn can_grow(blob: &Grid<bool>, (x, y): Position) -> bool {
+    // The 8 cells around, clockwise from the one above
+    const RING: [(isize, isize); 8] = [
+        (0, -1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+    ];
+    let ring = RING.map(|(dx, dy)| {
+        *blob
+            .get_signed(x as isize + dx, y as isize + dy)
+            .unwrap_or(&false)
+    });
+    // A corner only touching the cell diagonally would pinch the outline
+    let pinch = (1..8)
+        .step_by(2)
+        .any(|corner| ring[corner] && !ring[corner - 1] && !ring[(corner + 1) % 8]);
+    // Joining two separate stretches of the blob around the cell would enclose a hole
+    let stretches = (0..8).filter(|&i| ring[i] && !ring[(i + 7) % 8]).count();
+    !pinch && stretches == 1
+}
+
+/// A random loop through the tiles of a `width` by `height` grid, which never goes
+/// through the same tile twice: each tile of the loop is next to the tiles before and
+/// after it, and the last one is next to the first one. The loop goes clockwise, and
+/// can run along the sides of the grid.
+///
+/// The loop is the outline of a blob grown one square at a time on the grid, and
+/// the number of tiles it encloses is random.
+pub fn random_loop(rng: &mut Rng, width: usize, height: usize) -> Vec<Position> {
+    assert!(
+        width >= 2 && height >= 2,
+        "no loop fits in {}x{}",
+        width,
+        height
+    );
+    // The squares of the blob have the tiles of the grid as corners
+    let mut blob = Grid::new(width - 1, height - 1, false);
+    let squares = blob.width() * blob.height();
+    let target = (squares * rng.range(20..=70) / 100).max(1);
+    let start = (rng.index(blob.width()), rng.index(blob.height()));
+    blob[start] = true;
+    let mut area = 1;
+    let mut frontier: Vec<Position> = blob.neighbours4(start).collect();
+    while area < target && !frontier.is_empty() {
+        let square = frontier.swap_remove(rng.index(frontier.len()));
+        if blob[square] || !can_grow(&blob, square) {
+            continue;
+        }
+        blob[square] = true;
+        area += 1;
+        frontier.extend(blob.neighbours4(square).filter(|&next| !blob[next]));
+    }
+
+    // Follow the sides of the blob, with the blob on the right
+    let mut next = HashMap::new();
+    for ((x, y), _) in blob.iter().filter(|(_, &inside)| inside) {
+        let outside = |dx: isize, dy: isize| {
+            !blob
+                .get_signed(x as isize + dx, y as isize + dy)
+                .unwrap_or(&false)
+        };
+        if outside(0, -1) {
+            next.insert((x, y), (x + 1, y));
+        }
+        if outside(1, 0) {
+            next.insert((x + 1, y), (x + 1, y + 1));
+        }
+        if outside(0, 1) {
+            next.insert((x + 1, y + 1), (x, y + 1));
+        }
+        if outside(-1, 0) {
+            next.insert((x, y + 1), (x, y));
+        }
+    }
+    let mut tiles = vec![*next.keys().min().unwrap()];
+    loop {
+        let tile = next[tiles.last().unwrap()];
+        if tile == tiles[0] {
+            return tiles;
+        }
+        tiles.push(tile);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_seed() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(42), numbers(42));
+        assert_ne!(numbers(42), numbers(43));
+    }
+
+    #[test]
+    fn test_range() {
+        let mut rng = Rng::new(1);
+        let mut seen = [false; 5];
+        for _ in 0..1000 {
+            let n = rng.range(-2..=2_i32);
+            assert!((-2..=2).contains(&n));
+            seen[(n + 2) as usize] = true;
+        }
+        assert_eq!(seen, [true; 5]);
+        assert_eq!(rng.range(7..=7_usize), 7);
+        rng.range(i64::MIN..=i64::MAX);
+        let x = rng.float();
+        assert!((0.0..1.0).contains(&x));
+        let mut items = [1, 2, 3, 4, 5];
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_random_loop() {
+        for seed in 0..20 {
+            let mut rng = Rng::new(seed);
+            let tiles = random_loop(&mut rng, 12, 9);
+            let mut seen = Grid::new(12, 9, false);
+            for (i, &(x, y)) in tiles.iter().enumerate() {
+                assert!(!seen[(x, y)], "loop crosses itself at {:?}", (x, y));
+                seen[(x, y)] = true;
+                let (nx, ny) = tiles[(i + 1) % tiles.len()];
+                assert_eq!(x.abs_diff(nx) + y.abs_diff(ny), 1);
+            }
+            assert!(tiles.len() >= 4);
+        }
+    }
+}
@@ -0,0 +1,372 @@
+//! Interactive exploration of a parsed puzzle input, one command at a time.
+//!
+//! A day opens a `Session` on its input, with commands of its own, like following a
+//! part through the workflows of day 19. `run` reads the commands from the terminal,
+//! with a history and tab completion of the command names and of their arguments, or
+//! line by line when stdin is not a terminal:
+//!
+//! - tab: complete the word before the cursor, or list the possible completions
+//! - up and down: go through the previous commands
+//! - Ctrl-C: drop the line being typed
+//! - Ctrl-D, or `quit`: stop
+//!
+//! Like the keys of the animations, the terminal is read key by key with `stty`, on
+//! Unix-like systems only.
+
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+
+use crate::animation::stty;
+
+/// A command of a session, as shown by `help`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Command {
+    pub name: &'static str,
+    /// The arguments, like "<workflow>", or "[n]" when optional.
+    pub args: &'static str,
+    pub help: &'static str,
+}
+
+/// A parsed puzzle input, and the commands to explore it.
+pub trait Session {
+    fn commands(&self) -> &'static [Command];
+
+    /// The values which argument `index` of `command` can take, to complete it.
+    fn complete(&self, _command: &str, _index: usize) -> Vec<String> {
+        vec![]
+    }
+
+    /// Run `command` on `args`, and return what to print, or why it failed.
+    fn run(&mut self, command: &str, args: &[&str]) -> Result<String, String>;
+}
+
+/// Commands which every session has.
+const BUILTINS: &[Command] = &[
+    Command {
+        name: "help",
+        args: "",
+        help: "list the commands",
+    },
+    Command {
+        name: "quit",
+        args: "",
+        help: "stop exploring",
+    },
+];
+
+fn help(session: &dyn Session) -> String {
+    let commands: Vec<_> = session.commands().iter().chain(BUILTINS).collect();
+    let usage = |command: &Command| format!("{} {}", command.name, command.args);
+    let width = commands.iter().map(|c| usage(c).len()).max().unwrap_or(0);
+    let lines: Vec<_> = commands
+        .iter()
+        .map(|command| format!("{:width$}  {}", usage(command), command.help))
+        .collect();
+    lines.join("\n")
+}
+
+/// Error for `command` run with the wrong arguments, showing the ones it takes.
+pub fn wrong_arguments(session: &dyn Session, command: &str) -> String {
+    let args = session
+        .commands()
+        .iter()
+        .find(|c| c.name == command)
+        .map_or("", |c| c.args);
+    match args {
+        "" => format!("{} takes no arguments", command),
+        _ => format!("expected {} {}", command, args),
+    }
+}
+
+/// Run the command on a line, and return what to print, or `None` to stop.
+pub fn execute(session: &mut dyn Session, line: &str) -> Option<String> {
+    let words: Vec<_> = line.split_whitespace().collect();
+    let Some((&command, args)) = words.split_first() else {
+        return Some(String::new());
+    };
+    match command {
+        "quit" | "exit" => None,
+        "help" => Some(help(session)),
+        _ if session.commands().iter().any(|c| c.name == command) => Some(
+            session
+                .run(command, args)
+                .unwrap_or_else(|e| format!("error: {}", e)),
+        ),
+        _ => Some(format!(
+            "unknown command '{}', type help for the list of commands",
+            command
+        )),
+    }
+}
+
+/// What tab does to a line.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Completion {
+    /// The line, with its last word completed as far as all the candidates agree.
+    Line(String),
+    /// The candidates, when they do not agree on anything more.
+    Candidates(Vec<String>),
+}
+
+/// Complete the last word of `line`, from the command names for the first word, and
+/// from the session for the arguments.
+pub fn complete(session: &dyn Session, line: &str) -> Completion {
+    let words: Vec<_> = line.split_whitespace().collect();
+    // A line ending with a space starts a new word
+    let (done, word) = match words.split_last() {
+        Some((last, done)) if !line.ends_with(char::is_whitespace) => (done, *last),
+        _ => (&words[..], ""),
+    };
+    let mut candidates: Vec<String> = match done.split_first() {
+        None => session
+            .commands()
+            .iter()
+            .chain(BUILTINS)
+            .map(|command| command.name.to_string())
+            .collect(),
+        Some((command, args)) => session.complete(command, args.len()),
+    };
+    candidates.retain(|candidate| candidate.starts_with(word));
+    candidates.sort();
+    candidates.dedup();
+
+    let start = &line[..line.len() - word.len()];
+    match &candidates[..] {
+        [] => Completion::Line(line.to_string()),
+        [only] => Completion::Line(format!("{}{} ", start, only)),
+        [first, rest @ ..] => {
+            let common = rest.iter().fold(first.as_str(), |common, candidate| {
+                let end = common
+                    .char_indices()
+                    .zip(candidate.chars())
+                    .find(|((_, a), b)| a != b)
+                    .map_or(common.len().min(candidate.len()), |((i, _), _)| i);
+                &common[..end]
+            });
+            if common.len() > word.len() {
+                Completion::Line(format!("{}{}", start, common))
+            } else {
+                Completion::Candidates(candidates)
+            }
+        }
+    }
+}
+
+/// The terminal, read key by key while the session runs.
+struct RawMode;
+
+impl RawMode {
+    fn enter() -> Option<RawMode> {
+        stty(&["-icanon", "-echo", "-isig", "min", "1"]).then_some(RawMode)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        stty(&["icanon", "echo", "isig"]);
+    }
+}
+
+/// Read a line from the terminal, editing it with the keys, or `None` at the end.
+fn read_line(
+    session: &dyn Session,
+    prompt: &str,
+    history: &[String],
+    keys: &mut impl Iterator<Item = u8>,
+) -> Option<String> {
+    let mut line = Vec::new();
+    let mut back = 0;
+    let mut stdout = io::stdout();
+    let draw = |stdout: &mut io::Stdout, line: &[u8]| {
+        let _ = write!(
+            stdout,
+            "\r{}{}\x1b[K",
+            prompt,
+            String::from_utf8_lossy(line)
+        );
+        let _ = stdout.flush();
+    };
+    draw(&mut stdout, &line);
+    loop {
+        match keys.next()? {
+            b'\r' | b'\n' => {
+                println!();
+                return Some(String::from_utf8_lossy(&line).into_owned());
+            }
+            // Ctrl-D on an empty line
+            4 if line.is_empty() => {
+                println!();
+                return None;
+            }
+            // Ctrl-C
+            3 => {
+                println!("^C");
+                line.clear();
+            }
+            // Backspace, and the rest of a multi-byte character
+            8 | 127 => {
+                while let Some(byte) = line.pop() {
+                    if byte & 0b1100_0000 != 0b1000_0000 {
+                        break;
+                    }
+                }
+            }
+            b'\t' => match complete(session, &String::from_utf8_lossy(&line)) {
+                Completion::Line(completed) => line = completed.into_bytes(),
+                Completion::Candidates(candidates) => println!("\n{}", candidates.join("  ")),
+            },
+            // Escape sequences, of which only the up and down arrows are used
+            27 => {
+                if keys.next()? != b'[' {
+                    continue;
+                }
+                match keys.next()? {
+                    b'A' if back < history.len() => back += 1,
+                    b'B' if back > 0 => back -= 1,
+                    _ => continue,
+                }
+                line = match back {
+                    0 => Vec::new(),
+                    _ => history[history.len() - back].clone().into_bytes(),
+                };
+            }
+            key if key >= b' ' => line.push(key),
+            _ => {}
+        }
+        draw(&mut stdout, &line);
+    }
+}
+
+/// Run the commands typed on the terminal, or read from stdin, until the end of the
+/// input or `quit`.
+pub fn run(session: &mut dyn Session, prompt: &str) {
+    let raw = match io::stdin().is_terminal() {
+        true => RawMode::enter(),
+        false => None,
+    };
+    if raw.is_none() {
+        for line in io::stdin().lock().lines().map_while(Result::ok) {
+            match execute(session, &line) {
+                Some(output) if output.is_empty() => {}
+                Some(output) => println!("{}", output),
+                None => return,
+            }
+        }
+        return;
+    }
+
+    let mut keys = io::stdin().lock().bytes().map_while(Result::ok);
+    let mut history: Vec<String> = vec![];
+    while let Some(line) = read_line(session, prompt, &history, &mut keys) {
+        if !line.trim().is_empty() && history.last() != Some(&line) {
+            history.push(line.clone());
+        }
+        match execute(session, &line) {
+            Some(output) if output.is_empty() => {}
+            Some(output) => println!("{}", output),
+            None => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Counts up, with commands to add and to show the count
+    struct Counter(i64);
+
+    impl Session for Counter {
+        fn commands(&self) -> &'static [Command] {
+            &[
+                Command {
+                    name: "add",
+                    args: "<n>",
+                    help: "add n to the count",
+                },
+                Command {
+                    name: "show",
+                    args: "",
+                    help: "show the count",
+                },
+            ]
+        }
+
+        fn complete(&self, command: &str, index: usize) -> Vec<String> {
+            match (command, index) {
+                ("add", 0) => vec!["1".into(), "10".into(), "100".into(), "2".into()],
+                _ => vec![],
+            }
+        }
+
+        fn run(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+            match (command, args) {
+                ("add", [n]) => {
+                    self.0 += n.parse::<i64>().map_err(|e| e.to_string())?;
+                    Ok(String::new())
+                }
+                ("show", []) => Ok(self.0.to_string()),
+                _ => Err(wrong_arguments(self, command)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_execute() {
+        let mut counter = Counter(0);
+        assert_eq!(execute(&mut counter, "add 5"), Some(String::new()));
+        assert_eq!(execute(&mut counter, "  show "), Some("5".into()));
+        assert_eq!(
+            execute(&mut counter, "add"),
+            Some("error: expected add <n>".into())
+        );
+        assert_eq!(
+            execute(&mut counter, "show 1"),
+            Some("error: show takes no arguments".into())
+        );
+        assert_eq!(
+            execute(&mut counter, "add x"),
+            Some("error: invalid digit found in string".into())
+        );
+        assert!(execute(&mut counter, "sub 1")
+            .unwrap()
+            .starts_with("unknown command"));
+        assert_eq!(
+            execute(&mut counter, "help"),
+            Some(
+                "add <n>  add n to the count\nshow     show the count\n\
+                 help     list the commands\nquit     stop exploring"
+                    .into()
+            )
+        );
+        assert_eq!(execute(&mut counter, ""), Some(String::new()));
+        assert_eq!(execute(&mut counter, "quit"), None);
+    }
+
+    #[test]
+    fn test_complete() {
+        let counter = Counter(0);
+        let line = |line: &str| Completion::Line(line.into());
+        assert_eq!(complete(&counter, "sh"), line("show "));
+        assert_eq!(complete(&counter, "h"), line("help "));
+        assert_eq!(
+            complete(&counter, ""),
+            Completion::Candidates(vec![
+                "add".into(),
+                "help".into(),
+                "quit".into(),
+                "show".into()
+            ])
+        );
+        assert_eq!(complete(&counter, "add 2"), line("add 2 "));
+        assert_eq!(
+            complete(&counter, "add 1"),
+            Completion::Candidates(vec!["1".into(), "10".into(), "100".into()])
+        );
+        assert_eq!(
+            complete(&counter, "add 10"),
+            Completion::Candidates(vec!["10".into(), "100".into()])
+        );
+        assert_eq!(complete(&counter, "add 1 "), line("add 1 "));
+        assert_eq!(complete(&counter, "zzz"), line("zzz"));
+    }
+}
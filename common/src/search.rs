@@ -0,0 +1,240 @@
+//! Shortest path searches over implicit graphs: breadth-first search, Dijkstra and A*.
+//!
+//! The graph is never built: a search starts from a state and asks a `successors`
+//! function for the states reachable in one move, along with the cost of the move,
+//! until it reaches a state accepted by `is_goal`. A state can be anything hashable,
+//! such as a position together with the direction it was reached from.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Outcome of a successful search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult<S, C> {
+    /// States from the start to the goal, both included.
+    pub path: Vec<S>,
+    /// Total cost of the path. For a breadth-first search, its number of moves.
+    pub cost: C,
+    /// Number of states whose successors were generated, to compare searches.
+    pub expanded: usize,
+}
+
+/// States seen by a search, each with the index of the state it was reached from.
+struct Visited<S> {
+    states: Vec<(S, Option<usize>)>,
+    index: HashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash> Visited<S> {
+    fn new(start: S) -> Self {
+        Visited {
+            states: vec![(start.clone(), None)],
+            index: HashMap::from([(start, 0)]),
+        }
+    }
+
+    fn path(&self, mut idx: usize) -> Vec<S> {
+        let mut path = vec![];
+        loop {
+            let (state, parent) = &self.states[idx];
+            path.push(state.clone());
+            match parent {
+                Some(parent) => idx = *parent,
+                None => break,
+            }
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Breadth-first search, for graphs where every move costs the same. Returns the path
+/// with the fewest moves from `start` to a goal, or `None` if no goal is reachable.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<SearchResult<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut visited = Visited::new(start);
+    let mut queue = VecDeque::from([(0, 0)]);
+    let mut expanded = 0;
+    while let Some((idx, moves)) = queue.pop_front() {
+        let state = visited.states[idx].0.clone();
+        if is_goal(&state) {
+            return Some(SearchResult {
+                path: visited.path(idx),
+                cost: moves,
+                expanded,
+            });
+        }
+        expanded += 1;
+        for next in successors(&state) {
+            if let Entry::Vacant(entry) = visited.index.entry(next.clone()) {
+                entry.insert(visited.states.len());
+                queue.push_back((visited.states.len(), moves + 1));
+                visited.states.push((next, Some(idx)));
+            }
+        }
+    }
+    None
+}
+
+/// Dijkstra's algorithm: returns the cheapest path from `start` to a goal, or `None`
+/// if no goal is reachable. `successors` gives the states reachable in one move with
+/// the cost of the move, which must not be negative. `C::default()` is the cost of
+/// the empty path.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<SearchResult<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// A* search: Dijkstra's algorithm guided by a `heuristic` estimating the cost from a
+/// state to the goal, which expands fewer states. To find the cheapest path, the
+/// heuristic must never overestimate that cost, and must not decrease by more than
+/// the cost of a move, as the Manhattan distance on a grid where moves cost at
+/// least 1.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<SearchResult<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), 0))]);
+    let mut visited = Visited::new(start);
+    // Cost of the cheapest path found so far to each state, and whether it is final
+    let mut costs = vec![(C::default(), false)];
+    let mut expanded = 0;
+    while let Some(Reverse((_, idx))) = queue.pop() {
+        let (cost, done) = &mut costs[idx];
+        if *done {
+            // Already reached by a cheaper path
+            continue;
+        }
+        *done = true;
+        let cost = *cost;
+        let state = visited.states[idx].0.clone();
+        if is_goal(&state) {
+            return Some(SearchResult {
+                path: visited.path(idx),
+                cost,
+                expanded,
+            });
+        }
+        expanded += 1;
+        for (next, move_cost) in successors(&state) {
+            let next_cost = cost + move_cost;
+            let next_idx = match visited.index.entry(next) {
+                Entry::Occupied(entry) => {
+                    let next_idx = *entry.get();
+                    let (best, done) = costs[next_idx];
+                    if done || next_cost >= best {
+                        continue;
+                    }
+                    costs[next_idx].0 = next_cost;
+                    visited.states[next_idx].1 = Some(idx);
+                    next_idx
+                }
+                Entry::Vacant(entry) => {
+                    let next_idx = visited.states.len();
+                    visited.states.push((entry.key().clone(), Some(idx)));
+                    entry.insert(next_idx);
+                    costs.push((next_cost, false));
+                    next_idx
+                }
+            };
+            let estimate = next_cost + heuristic(&visited.states[next_idx].0);
+            queue.push(Reverse((estimate, next_idx)));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Grid;
+
+    const MAZE: &str = "\
+..#....
+.##.##.
+...#...
+.#...#.
+...#.#.";
+
+    fn open_neighbours(grid: &Grid<char>, position: (usize, usize)) -> Vec<(usize, usize)> {
+        grid.neighbours4(position)
+            .filter(|&next| grid[next] == '.')
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = Grid::parse(MAZE, Some).unwrap();
+        let goal = (6, 4);
+        let result = bfs((0, 0), |&p| open_neighbours(&grid, p), |&p| p == goal).unwrap();
+        assert_eq!(result.cost, 12);
+        assert_eq!(result.path.len(), 13);
+        assert_eq!(result.path.first(), Some(&(0, 0)));
+        assert_eq!(result.path.last(), Some(&goal));
+        assert!(result
+            .path
+            .windows(2)
+            .all(|step| open_neighbours(&grid, step[0]).contains(&step[1])));
+        assert_eq!(
+            bfs((0, 0), |&p| open_neighbours(&grid, p), |&p| p == (2, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // The direct edge from 0 to 3 is more expensive than the detour
+        let edges: [&[(u8, u32)]; 4] = [&[(1, 1), (3, 10)], &[(2, 2)], &[(3, 3)], &[]];
+        let successors = |&node: &u8| edges[node as usize].iter().copied();
+        let result = dijkstra(0, successors, |&node| node == 3).unwrap();
+        assert_eq!(result.path, [0, 1, 2, 3]);
+        assert_eq!(result.cost, 6);
+        assert_eq!(result.expanded, 3);
+
+        let result = dijkstra(0, successors, |&node| node == 0).unwrap();
+        assert_eq!((result.path, result.cost, result.expanded), (vec![0], 0, 0));
+        assert_eq!(dijkstra(3, successors, |&node| node == 0), None);
+    }
+
+    #[test]
+    fn test_astar() {
+        let grid = Grid::parse(MAZE, Some).unwrap();
+        let goal: (usize, usize) = (6, 4);
+        let successors = |&p: &(usize, usize)| {
+            open_neighbours(&grid, p)
+                .into_iter()
+                .map(|next| (next, 1_usize))
+        };
+        let manhattan = |&(x, y): &(usize, usize)| x.abs_diff(goal.0) + y.abs_diff(goal.1);
+        let guided = astar((0, 0), successors, manhattan, |&p| p == goal).unwrap();
+        let blind = dijkstra((0, 0), successors, |&p| p == goal).unwrap();
+        assert_eq!(guided.cost, 12);
+        assert_eq!(blind.cost, 12);
+        assert!(guided.expanded < blind.expanded);
+    }
+}